@@ -1,9 +1,15 @@
+use crate::locale::{format_message, Messages, EN};
+use crate::param::{RangeValue, ValueType};
+use crate::utils::render_did_you_mean;
+
 use indexmap::IndexMap;
+use serde::{Serialize, Serializer};
+use std::fmt;
 
 #[cfg(feature = "eval-bash")]
 use crate::utils::{
     argc_var_name, escape_shell_words, AFTER_HOOK, ARGC_LOAD_DOTENV, ARGC_REQUIRE_TOOLS,
-    BEFORE_HOOK, VARIABLE_PREFIX,
+    ARGC_UP_TO_DATE, BEFORE_HOOK, VARIABLE_PREFIX,
 };
 
 #[derive(Debug, PartialEq, Eq)]
@@ -21,9 +27,471 @@ pub enum ArgcValue {
     Hook((bool, bool)),
     Dotenv(String),
     RequireTools(Vec<String>),
+    Deps((Vec<Vec<String>>, bool)),
     CommandFn(String),
+    UpToDate((String, String)),
+    UpToDateCheck((String, Vec<String>, Vec<String>)),
     ParamFn(String),
-    Error((String, i32)),
+    Warning(String),
+    Error((String, i32, MatchError)),
+}
+
+/// Why the args didn't lead to a command function, carried by `ArgcValue::Error` next to the rendered message.
+///
+/// `level` is the level of the command the param belongs to, 0 for the root command.
+/// `Display` renders the English wording, except for help and version, which are rendered from the command.
+/// The message of `ArgcValue::Error` follows `@meta locale`, or else `LC_ALL`, `LC_MESSAGES` and `LANG`.
+/// To customize the wording, match on the error and replace the message of `ArgcValue::Error` before `to_bash`.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum MatchError {
+    DisplayHelp,
+    #[non_exhaustive]
+    DisplaySubcommandHelp {
+        name: String,
+    },
+    DisplayVersion,
+    /// `arg` is the offending arg, if any, and `subcommands` the subcommands of `cmd`
+    #[non_exhaustive]
+    InvalidSubcommand {
+        arg: Option<String>,
+        cmd: String,
+        subcommands: Vec<String>,
+    },
+    /// `suggestion` is a similar flag or option
+    #[non_exhaustive]
+    UnknownArgument {
+        level: usize,
+        arg: String,
+        suggestion: Option<String>,
+    },
+    #[non_exhaustive]
+    MissingRequiredArguments {
+        level: usize,
+        params: Vec<String>,
+    },
+    #[non_exhaustive]
+    MissingRequiredEnvironments {
+        envs: Vec<String>,
+    },
+    #[non_exhaustive]
+    NotMultipleArgument {
+        level: usize,
+        param: String,
+    },
+    #[non_exhaustive]
+    InvalidValue {
+        level: usize,
+        value: String,
+        param: String,
+        choices: Vec<String>,
+    },
+    #[non_exhaustive]
+    InvalidBindEnvironment {
+        level: usize,
+        value: String,
+        env: String,
+        param: String,
+        choices: Vec<String>,
+    },
+    #[non_exhaustive]
+    InvalidEnvironment {
+        level: usize,
+        value: String,
+        env: String,
+        choices: Vec<String>,
+    },
+    #[non_exhaustive]
+    MismatchValues {
+        level: usize,
+        param: String,
+    },
+    #[non_exhaustive]
+    NoFlagValue {
+        level: usize,
+        param: String,
+    },
+    /// `target` is the rendered param or environment variable the value was given to
+    #[non_exhaustive]
+    InvalidValueType {
+        level: usize,
+        value: String,
+        target: String,
+        value_type: ValueType,
+    },
+    #[non_exhaustive]
+    ValueOutOfRange {
+        level: usize,
+        value: String,
+        target: String,
+        range: RangeValue,
+    },
+    #[non_exhaustive]
+    InvalidValueLength {
+        level: usize,
+        value: String,
+        target: String,
+        length: RangeValue,
+    },
+    #[non_exhaustive]
+    InvalidValuePattern {
+        level: usize,
+        value: String,
+        target: String,
+        pattern: String,
+    },
+    #[non_exhaustive]
+    ConflictArguments {
+        level: usize,
+        param: String,
+        other: String,
+    },
+    /// `requires` are the missing params that `param` requires
+    #[non_exhaustive]
+    MissingDependentArguments {
+        level: usize,
+        param: String,
+        requires: Vec<String>,
+    },
+    #[non_exhaustive]
+    MissingOneOfArguments {
+        level: usize,
+        params: Vec<String>,
+    },
+}
+
+impl MatchError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            MatchError::DisplayHelp
+            | MatchError::DisplaySubcommandHelp { .. }
+            | MatchError::DisplayVersion => 0,
+            _ => 1,
+        }
+    }
+
+    pub(crate) fn render(&self, messages: &Messages) -> String {
+        let to_list = |values: &[String]| {
+            values
+                .iter()
+                .map(|v| format!("  {v}"))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        let invalid_value = |value: &str, target: &str| {
+            format_message(
+                messages.invalid_value,
+                &[("value", value), ("target", target)],
+            )
+        };
+        let possible_values = |choices: &[String]| {
+            format_message(messages.possible_values, &[("values", &choices.join(", "))])
+        };
+        match self {
+            MatchError::DisplayHelp => "help requested".to_string(),
+            MatchError::DisplaySubcommandHelp { name } => format!("help of `{name}` requested"),
+            MatchError::DisplayVersion => "version requested".to_string(),
+            MatchError::InvalidSubcommand {
+                arg,
+                cmd,
+                subcommands,
+            } => {
+                let (message, hint) = match arg {
+                    Some(arg) => (
+                        format_message(messages.invalid_subcommand, &[("cmd", cmd), ("arg", arg)]),
+                        render_did_you_mean(messages, arg, subcommands),
+                    ),
+                    None => (
+                        format_message(messages.missing_subcommand, &[("cmd", cmd)]),
+                        String::new(),
+                    ),
+                };
+                let subcommands = subcommands.join(", ");
+                format!(
+                    "{message}\n  {}{hint}",
+                    format_message(messages.subcommand_list, &[("subcommands", &subcommands)])
+                )
+            }
+            MatchError::UnknownArgument {
+                arg, suggestion, ..
+            } => {
+                let message = format_message(messages.unknown_argument, &[("name", arg)]);
+                match suggestion {
+                    Some(suggestion) => format!(
+                        "{message}\n  {}",
+                        format_message(messages.did_you_mean, &[("value", suggestion)])
+                    ),
+                    None => message,
+                }
+            }
+            MatchError::MissingRequiredArguments { params, .. } => {
+                format!(
+                    "{}\n{}",
+                    messages.missing_required_arguments,
+                    to_list(params)
+                )
+            }
+            MatchError::MissingRequiredEnvironments { envs } => {
+                format!(
+                    "{}\n{}",
+                    messages.missing_required_environments,
+                    to_list(envs)
+                )
+            }
+            MatchError::NotMultipleArgument { param, .. } => {
+                format_message(messages.not_multiple_argument, &[("name", param)])
+            }
+            MatchError::InvalidValue {
+                value,
+                param,
+                choices,
+                ..
+            } => {
+                format!(
+                    "{}\n  {}{}",
+                    invalid_value(value, &format!("`{param}`")),
+                    possible_values(choices),
+                    render_did_you_mean(messages, value, choices)
+                )
+            }
+            MatchError::InvalidBindEnvironment {
+                value,
+                env,
+                param,
+                choices,
+                ..
+            } => {
+                let args = [("env", env.as_str()), ("name", param.as_str())];
+                if choices.is_empty() {
+                    format_message(messages.invalid_bind_environment, &args)
+                } else {
+                    let target = format_message(messages.bind_env_target, &args);
+                    format!(
+                        "{}\n  {}",
+                        invalid_value(value, &target),
+                        possible_values(choices)
+                    )
+                }
+            }
+            MatchError::InvalidEnvironment {
+                value,
+                env,
+                choices,
+                ..
+            } => {
+                let target = format_message(messages.env_target, &[("name", env)]);
+                format!(
+                    "{}\n  {}",
+                    invalid_value(value, &target),
+                    possible_values(choices)
+                )
+            }
+            MatchError::MismatchValues { param, .. } => {
+                format_message(messages.mismatch_values, &[("name", param)])
+            }
+            MatchError::NoFlagValue { param, .. } => {
+                format_message(messages.no_flag_value, &[("name", param)])
+            }
+            MatchError::InvalidValueType {
+                value,
+                target,
+                value_type,
+                ..
+            } => {
+                format!(
+                    "{}\n  {}",
+                    invalid_value(value, target),
+                    format_message(messages.expected, &[("expected", value_type.expected())])
+                )
+            }
+            MatchError::ValueOutOfRange {
+                value,
+                target,
+                range,
+                ..
+            } => {
+                format!(
+                    "{}\n  {}",
+                    invalid_value(value, target),
+                    format_message(messages.range, &[("range", &range.to_string())])
+                )
+            }
+            MatchError::InvalidValueLength {
+                value,
+                target,
+                length,
+                ..
+            } => {
+                format!(
+                    "{}\n  {}",
+                    invalid_value(value, target),
+                    format_message(messages.length, &[("length", &length.to_string())])
+                )
+            }
+            MatchError::InvalidValuePattern {
+                value,
+                target,
+                pattern,
+                ..
+            } => {
+                format!(
+                    "{}\n  {}",
+                    invalid_value(value, target),
+                    format_message(messages.pattern, &[("pattern", pattern)])
+                )
+            }
+            MatchError::ConflictArguments { param, other, .. } => format_message(
+                messages.conflict_arguments,
+                &[("name", param), ("other", other)],
+            ),
+            MatchError::MissingDependentArguments {
+                param, requires, ..
+            } => {
+                format!(
+                    "{}\n{}",
+                    format_message(messages.missing_dependent_arguments, &[("name", param)]),
+                    to_list(requires)
+                )
+            }
+            MatchError::MissingOneOfArguments { params, .. } => {
+                format!("{}\n{}", messages.missing_one_of_arguments, to_list(params))
+            }
+        }
+    }
+}
+
+impl fmt::Display for MatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(&EN))
+    }
+}
+
+/// The json form of `ArgcValue`, tagged by `type` with named fields.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum JsonValue<'a> {
+    Single {
+        id: &'a str,
+        value: &'a str,
+    },
+    SingleFn {
+        id: &'a str,
+        value: &'a str,
+    },
+    Multiple {
+        id: &'a str,
+        value: &'a [String],
+    },
+    Map {
+        id: &'a str,
+        value: &'a IndexMap<String, Vec<String>>,
+    },
+    PositionalSingle {
+        id: &'a str,
+        value: &'a str,
+    },
+    PositionalSingleFn {
+        id: &'a str,
+        value: &'a str,
+    },
+    PositionalMultiple {
+        id: &'a str,
+        value: &'a [String],
+    },
+    ExtraPositionalMultiple {
+        value: &'a [String],
+    },
+    Env {
+        id: &'a str,
+        value: &'a str,
+    },
+    EnvFn {
+        id: &'a str,
+        value: &'a str,
+    },
+    Hook {
+        before: bool,
+        after: bool,
+    },
+    Dotenv {
+        path: &'a str,
+    },
+    RequireTools {
+        tools: &'a [String],
+    },
+    Deps {
+        stages: &'a [Vec<String>],
+        parallel: bool,
+    },
+    CommandFn {
+        name: &'a str,
+    },
+    UpToDate {
+        name: &'a str,
+        message: &'a str,
+    },
+    UpToDateCheck {
+        message: &'a str,
+        targets: &'a [String],
+        sources: &'a [String],
+    },
+    ParamFn {
+        name: &'a str,
+    },
+    Warning {
+        message: &'a str,
+    },
+    Error {
+        #[serde(flatten)]
+        error: &'a MatchError,
+        exit: i32,
+        message: &'a str,
+    },
+}
+
+impl Serialize for ArgcValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = match self {
+            ArgcValue::Single(id, value) => JsonValue::Single { id, value },
+            ArgcValue::SingleFn(id, value) => JsonValue::SingleFn { id, value },
+            ArgcValue::Multiple(id, value) => JsonValue::Multiple { id, value },
+            ArgcValue::Map(id, value) => JsonValue::Map { id, value },
+            ArgcValue::PositionalSingle(id, value) => JsonValue::PositionalSingle { id, value },
+            ArgcValue::PositionalSingleFn(id, value) => JsonValue::PositionalSingleFn { id, value },
+            ArgcValue::PositionalMultiple(id, value) => JsonValue::PositionalMultiple { id, value },
+            ArgcValue::ExtraPositionalMultiple(value) => {
+                JsonValue::ExtraPositionalMultiple { value }
+            }
+            ArgcValue::Env(id, value) => JsonValue::Env { id, value },
+            ArgcValue::EnvFn(id, value) => JsonValue::EnvFn { id, value },
+            ArgcValue::Hook((before, after)) => JsonValue::Hook {
+                before: *before,
+                after: *after,
+            },
+            ArgcValue::Dotenv(path) => JsonValue::Dotenv { path },
+            ArgcValue::RequireTools(tools) => JsonValue::RequireTools { tools },
+            ArgcValue::Deps((stages, parallel)) => JsonValue::Deps {
+                stages,
+                parallel: *parallel,
+            },
+            ArgcValue::CommandFn(name) => JsonValue::CommandFn { name },
+            ArgcValue::UpToDate((name, message)) => JsonValue::UpToDate { name, message },
+            ArgcValue::UpToDateCheck((message, targets, sources)) => JsonValue::UpToDateCheck {
+                message,
+                targets,
+                sources,
+            },
+            ArgcValue::ParamFn(name) => JsonValue::ParamFn { name },
+            ArgcValue::Warning(message) => JsonValue::Warning { message },
+            ArgcValue::Error((message, exit, error)) => JsonValue::Error {
+                error,
+                exit: *exit,
+                message,
+            },
+        };
+        value.serialize(serializer)
+    }
 }
 
 #[cfg(feature = "eval-bash")]
@@ -34,6 +502,8 @@ impl ArgcValue {
         let mut exit = false;
         let mut positional_args = vec![];
         let mut require_tools = vec![];
+        let mut deps = vec![];
+        let mut up_to_date = String::new();
         let (mut before_hook, mut after_hook) = (false, false);
         for value in values {
             match value {
@@ -116,6 +586,19 @@ impl ArgcValue {
                 ArgcValue::RequireTools(tools) => {
                     require_tools = tools.to_vec();
                 }
+                ArgcValue::Deps((stages, parallel)) => {
+                    // A failing dep stops the recipe with its exit status
+                    for stage in stages {
+                        if *parallel && stage.len() > 1 {
+                            deps.push(format!(
+                                r#"argc --argc-parallel "$0" {} || exit $?"#,
+                                stage.join(" ::: ")
+                            ));
+                        } else {
+                            deps.extend(stage.iter().map(|dep| format!("{dep} || exit $?")));
+                        }
+                    }
+                }
                 ArgcValue::CommandFn(name) => {
                     if positional_args.is_empty() {
                         last = name.to_string();
@@ -132,7 +615,32 @@ impl ArgcValue {
                     }
                     exit = true;
                 }
-                ArgcValue::Error((error, exit)) => {
+                ArgcValue::Warning(warning) => {
+                    list.push(format!("command cat >&2 <<-'EOF' \n{warning}\nEOF"));
+                }
+                ArgcValue::UpToDate((_, message)) => {
+                    return format!("command cat >&2 <<-'EOF' \n{message}\nEOF\nexit 0")
+                }
+                ArgcValue::UpToDateCheck((message, targets, sources)) => {
+                    let to_words = |values: &[String]| {
+                        values
+                            .iter()
+                            .map(|v| escape_shell_words(v))
+                            .collect::<Vec<String>>()
+                            .join(" ")
+                    };
+                    let (targets, sources) = (to_words(targets), to_words(sources));
+                    up_to_date = format!(
+                        r#"{ARGC_UP_TO_DATE}
+if _argc_up_to_date {targets} -- {sources}; then
+command cat >&2 <<-'EOF' 
+{message}
+EOF
+exit 0
+fi"#
+                    );
+                }
+                ArgcValue::Error((error, exit, _)) => {
                     return format!("command cat >&2 <<-'EOF' \n{}\nEOF\nexit {}", error, exit)
                 }
             }
@@ -157,6 +665,10 @@ impl ArgcValue {
             list.push(BEFORE_HOOK.to_string())
         }
         if !last.is_empty() {
+            list.extend(deps);
+            if !up_to_date.is_empty() {
+                list.push(up_to_date);
+            }
             list.push(last);
             if after_hook {
                 list.push(AFTER_HOOK.to_string())
@@ -167,4 +679,139 @@ impl ArgcValue {
         }
         list.join("\n")
     }
+
+    /// Describe what `to_bash` would do in a human-readable table, without running anything.
+    pub(crate) fn to_dry_run(values: &[Self], messages: &Messages) -> String {
+        let mut rows: Vec<(&str, String)> = vec![];
+        let mut variables: Vec<(String, String)> = vec![];
+        let mut envs: Vec<(String, String)> = vec![];
+        let mut positional_args = vec![];
+        let mut hooks = vec![];
+        let mut warnings = vec![];
+        let to_array = |values: &[String]| {
+            let values: Vec<String> = values.iter().map(|v| escape_shell_words(v)).collect();
+            format!("( {} )", values.join(" "))
+        };
+        for value in values {
+            match value {
+                ArgcValue::Single(id, value) => {
+                    variables.push((argc_var_name(id), escape_shell_words(value)));
+                }
+                ArgcValue::SingleFn(id, fn_name) => {
+                    variables.push((argc_var_name(id), format!("`{fn_name}`")));
+                }
+                ArgcValue::Multiple(id, values) => {
+                    variables.push((argc_var_name(id), to_array(values)));
+                }
+                ArgcValue::Map(id, map) => {
+                    let var_name = argc_var_name(id);
+                    variables.extend(map.iter().map(|(k, v)| {
+                        let v: Vec<String> = v.iter().map(|x| escape_shell_words(x)).collect();
+                        (format!(r#"{var_name}["{k}"]"#), v.join("|"))
+                    }));
+                }
+                ArgcValue::PositionalSingle(id, value) => {
+                    variables.push((argc_var_name(id), escape_shell_words(value)));
+                    positional_args.push(value.clone());
+                }
+                ArgcValue::PositionalSingleFn(id, fn_name) => {
+                    variables.push((argc_var_name(id), format!("`{fn_name}`")));
+                    positional_args.push(format!("`{fn_name}`"));
+                }
+                ArgcValue::PositionalMultiple(id, values) => {
+                    variables.push((argc_var_name(id), to_array(values)));
+                    positional_args.extend(values.iter().cloned());
+                }
+                ArgcValue::ExtraPositionalMultiple(values) => {
+                    positional_args.extend(values.iter().cloned());
+                }
+                ArgcValue::Env(name, value) => {
+                    envs.push((name.clone(), escape_shell_words(value)));
+                }
+                ArgcValue::EnvFn(name, fn_name) => {
+                    envs.push((name.clone(), format!("`{fn_name}`")));
+                }
+                ArgcValue::Hook((before, after)) => {
+                    if *before {
+                        hooks.push(BEFORE_HOOK);
+                    }
+                    if *after {
+                        hooks.push(AFTER_HOOK);
+                    }
+                }
+                ArgcValue::Dotenv(value) => {
+                    rows.push((messages.dry_run_dotenv, value.clone()));
+                }
+                ArgcValue::RequireTools(tools) => {
+                    rows.push((messages.dry_run_require_tools, tools.join(", ")));
+                }
+                ArgcValue::Deps((stages, parallel)) => {
+                    let separator = if *parallel { " & " } else { ", " };
+                    let stages: Vec<String> = stages.iter().map(|v| v.join(separator)).collect();
+                    rows.push((messages.dry_run_dependencies, stages.join(" -> ")));
+                }
+                ArgcValue::CommandFn(name) => {
+                    rows.insert(0, (messages.dry_run_function, name.clone()));
+                    variables.push((format!("{VARIABLE_PREFIX}_fn"), name.clone()));
+                }
+                ArgcValue::ParamFn(name) => {
+                    rows.insert(0, (messages.dry_run_function, name.clone()));
+                }
+                ArgcValue::Warning(warning) => {
+                    warnings.push(warning.clone());
+                }
+                ArgcValue::UpToDate((name, _)) => {
+                    return format_message(messages.dry_run_up_to_date, &[("name", name)]);
+                }
+                ArgcValue::UpToDateCheck((_, targets, _)) => {
+                    rows.push((messages.dry_run_skip_up_to_date, targets.join(", ")));
+                }
+                ArgcValue::Error((error, exit, _)) => {
+                    let exit = exit.to_string();
+                    let header = format_message(messages.dry_run_error, &[("exit", &exit)]);
+                    return format!("{header}\n{error}");
+                }
+            }
+        }
+        if !hooks.is_empty() {
+            rows.push((messages.dry_run_hooks, hooks.join(", ")));
+        }
+        if !positional_args.is_empty() {
+            rows.push((messages.dry_run_arguments, to_array(&positional_args)));
+        }
+        variables.push((
+            format!("{VARIABLE_PREFIX}_positionals"),
+            to_array(&positional_args),
+        ));
+
+        let mut output = vec![];
+        let width = rows
+            .iter()
+            .map(|(k, _)| k.chars().count())
+            .max()
+            .unwrap_or_default();
+        output.extend(rows.iter().map(|(k, v)| format!("{k:<width$}  {v}")));
+        for (title, items) in [
+            (messages.dry_run_variables, &variables),
+            (messages.dry_run_environments, &envs),
+        ] {
+            if items.is_empty() {
+                continue;
+            }
+            if !output.is_empty() {
+                output.push(String::new());
+            }
+            output.push(format!("{title}:"));
+            let width = items.iter().map(|(k, _)| k.len()).max().unwrap_or_default();
+            output.extend(items.iter().map(|(k, v)| format!("  {k:<width$}  {v}")));
+        }
+        for warning in warnings {
+            output.push(String::new());
+            output.push(format_message(
+                messages.dry_run_warning,
+                &[("message", &warning)],
+            ));
+        }
+        output.join("\n")
+    }
 }
@@ -1,12 +1,18 @@
 # @option --argc-eval~ <FILE> <ARGS>                Use `eval "$(argc --argc-eval "$0" "$@")"`
+# @option --argc-eval-json~ <FILE> <ARGS>           Print the evaluated arguments as json
+# @option --argc-dry-run~ <FILE> <ARGS>             Print what would run without executing it
 # @option --argc-create~ <RECIPES>                  Create a boilerplate argcfile
 # @option --argc-run~ <FILE> <ARGS>                 Run an argc-based script
 # @option --argc-build <FILE> <OUTPATH?>            Generate bashscript without argc dependency
 # @option --argc-mangen <FILE> <OUTDIR>             Generate man pages
+# @option --argc-markdown <FILE> <OUTDIR>           Generate markdown docs
 # @option --argc-completions <SHELL> <CMDS>         Generate shell completion scripts
 # @option --argc-compgen <SHELL> <FILE> <ARGS>      Generate completion candidates
 # @option --argc-export <FILE>                      Export command line definitions as json
+# @option --argc-import <JSON>                      Generate an argcfile from the json of --argc-export
+# @option --argc-schema <FILE?> <CMDS>              Print json schema of the export format or a command's arguments
 # @option --argc-parallel~ <FILE> <ARGS>            Run functions in parallel
+# @option --argc-watch~ <PATHS> <ARGS>              Rerun a recipe whenever the paths change
 # @flag --argc-script-path                          Print current argcfile path
 # @flag --argc-shell-path                           Print current shell path
 # @flag --argc-help                                 Print help information
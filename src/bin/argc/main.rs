@@ -1,4 +1,7 @@
 mod parallel;
+mod process_group;
+mod trace;
+mod watch;
 
 use anyhow::{anyhow, bail, Context, Result};
 use argc::{compgen_kind, utils::*, CompKind, NativeRuntime, Runtime, Shell, COMPGEN_KIND_SYMBOL};
@@ -93,6 +96,30 @@ fn run() -> Result<i32> {
                 let args = [vec![&script_path], args.iter().skip(3).collect()].concat();
                 return run_command(&script_path, &shell, &args, envs, cwd);
             }
+            "--argc-eval-json" => {
+                let (source, script_path, cmd_args) = parse_script_args(&args[2..])?;
+                let values = argc::eval(
+                    runtime,
+                    &source,
+                    &cmd_args,
+                    Some(&script_path),
+                    get_term_width(),
+                )?;
+                println!("{}", serde_json::to_string_pretty(&values)?);
+            }
+            "--argc-dry-run" => {
+                let (source, script_path, cmd_args) = parse_script_args(&args[2..])?;
+                let (output, code) = argc::dry_run(
+                    runtime,
+                    &source,
+                    &cmd_args,
+                    Some(&script_path),
+                    get_term_width(),
+                )?;
+                println!("{output}");
+                // Exit like the run would, e.g. on invalid args
+                return Ok(code);
+            }
             "--argc-create" => {
                 if let Some((_, script_file)) = get_script_path(false) {
                     bail!("Already exist {}", script_file.display());
@@ -137,6 +164,18 @@ fn run() -> Result<i32> {
                     println!("saved {}", outfile.display());
                 }
             }
+            "--argc-markdown" => {
+                let (source, _script_path, cmd_args) = parse_script_args(&args[2..])?;
+                let outdir = cmd_args.get(1).ok_or_else(|| anyhow!("No output dir"))?;
+                let pages = argc::markdown(&source, &cmd_args[0])?;
+                let outdir = ensure_outdir(outdir).with_context(|| "Invalid output dir")?;
+                for (filename, page) in pages {
+                    let outfile = outdir.join(filename);
+                    fs::write(&outfile, page)
+                        .with_context(|| format!("Failed to write '{}'", outfile.display()))?;
+                    println!("saved {}", outfile.display());
+                }
+            }
             "--argc-completions" => {
                 let shell: Shell = match args.get(2) {
                     Some(v) => v.parse()?,
@@ -154,6 +193,23 @@ fn run() -> Result<i32> {
                 let value = argc::export(&source, &cmd_args[0])?;
                 println!("{}", serde_json::to_string_pretty(&value)?);
             }
+            "--argc-import" => {
+                let path = args
+                    .get(2)
+                    .ok_or_else(|| anyhow!("Usage: argc --argc-import <JSON>"))?;
+                let json = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to load json at '{path}'"))?;
+                print!("{}", argc::import(&json)?);
+            }
+            "--argc-schema" => {
+                if args.len() == 2 {
+                    print!("{}", argc::EXPORT_SCHEMA);
+                } else {
+                    let (source, _script_path, cmd_args) = parse_script_args(&args[2..])?;
+                    let value = argc::schema(&source, &cmd_args)?;
+                    println!("{}", serde_json::to_string_pretty(&value)?);
+                }
+            }
             "--argc-parallel" => {
                 if args.len() <= 3 {
                     bail!("Usage: argc --argc-parallel <SCRIPT> <ARGS>...");
@@ -165,6 +221,24 @@ fn run() -> Result<i32> {
                 }
                 parallel::parallel(runtime, &shell, &script_path, &cmd_args[1..])?;
             }
+            "--argc-watch" => {
+                let (paths, cmd_args) = match args[2..].iter().position(|v| v == "--") {
+                    Some(index) if index > 0 => (&args[2..2 + index], &args[3 + index..]),
+                    _ => bail!("Usage: argc --argc-watch <PATHS>... -- <ARGS>..."),
+                };
+                let shell = runtime.shell_path()?;
+                let (script_dir, script_file) = get_script_path(true).ok_or_else(|| {
+                    anyhow!("Argcfile not found, try `argc --argc-help` for help.")
+                })?;
+                let mut envs = HashMap::new();
+                if let Some(cwd) = runtime.current_dir() {
+                    if env::var("ARGC_PWD").is_err() {
+                        envs.insert("ARGC_PWD".to_string(), cwd);
+                    }
+                }
+                let script_file = script_file.display().to_string();
+                watch::watch(&shell, &script_file, &script_dir, paths, cmd_args, envs)?;
+            }
             "--argc-script-path" => {
                 let (_, script_file) =
                     get_script_path(true).ok_or_else(|| anyhow!("Argcfile not found."))?;
@@ -196,11 +270,91 @@ fn run() -> Result<i32> {
             }
         }
         let script_file = script_file.display().to_string();
+        let recipe = eval_recipe(runtime, &script_file, &args[1..]);
+        let cache_file = recipe.as_ref().and_then(|(source, cmd_args, values)| {
+            get_cache_file(runtime, source, &cmd_args[0], values, &script_dir)
+        });
+        let trace = recipe
+            .as_ref()
+            .and_then(|(_, cmd_args, values)| trace::Trace::new(runtime, cmd_args, values));
+        if let Some(trace) = &trace {
+            let (key, value) = trace.env();
+            envs.insert(key, value);
+        }
         let args = [vec![&script_file], args[1..].iter().collect()].concat();
-        run_command(&script_file, &shell, &args, envs, Some(&script_dir))
+        let code = match cache_file {
+            Some((message, cache_file)) => {
+                if cache_file.exists() {
+                    eprintln!("{message}");
+                    0
+                } else {
+                    let code = wait_command(&script_file, &shell, &args, envs, Some(&script_dir))?;
+                    if code == 0 {
+                        if let Some(dir) = cache_file.parent() {
+                            fs::create_dir_all(dir).with_context(|| {
+                                format!("Failed to create cache dir '{}'", dir.display())
+                            })?;
+                        }
+                        fs::write(&cache_file, "").with_context(|| {
+                            format!("Failed to write cache file '{}'", cache_file.display())
+                        })?;
+                    }
+                    code
+                }
+            }
+            None if trace.is_some() => {
+                wait_command(&script_file, &shell, &args, envs, Some(&script_dir))?
+            }
+            None => return run_command(&script_file, &shell, &args, envs, Some(&script_dir)),
+        };
+        if let Some(trace) = trace {
+            trace.finish(code)?;
+        }
+        Ok(code)
     }
 }
 
+/// Read the script and evaluate the recipe that the args would run, only when tracing or caching
+/// needs it since the script evaluates its args again once it runs
+fn eval_recipe(
+    runtime: NativeRuntime,
+    script_file: &str,
+    args: &[String],
+) -> Option<(String, Vec<String>, Vec<argc::ArgcValue>)> {
+    let source = fs::read_to_string(script_file).ok()?;
+    if !trace::Trace::is_enabled(runtime) && !has_meta_cache(&source) {
+        return None;
+    }
+    let name = get_script_name(script_file).ok()?;
+    let name = name.strip_suffix(".sh").unwrap_or(name);
+    let cmd_args = [vec![name.to_string()], args.to_vec()].concat();
+    let values = argc::eval(runtime, &source, &cmd_args, Some(script_file), None).ok()?;
+    Some((source, cmd_args, values))
+}
+
+/// Whether any command of the script is marked with `@meta cache`
+fn has_meta_cache(source: &str) -> bool {
+    source.lines().any(|line| {
+        line.trim_start()
+            .strip_prefix('#')
+            .and_then(|v| v.trim_start().strip_prefix("@meta"))
+            .is_some_and(|v| v.split_whitespace().next() == Some("cache"))
+    })
+}
+
+/// Locate the cache file of the recipe to run, if it's cacheable
+fn get_cache_file(
+    runtime: NativeRuntime,
+    source: &str,
+    root_name: &str,
+    values: &[argc::ArgcValue],
+    script_dir: &Path,
+) -> Option<(String, PathBuf)> {
+    let cwd = script_dir.display().to_string();
+    let (message, key) = argc::cache_key(runtime, source, root_name, values, &cwd).ok()??;
+    Some((message, script_dir.join(argc::CACHE_DIR).join(key)))
+}
+
 fn run_eval(runtime: NativeRuntime, args: Vec<String>) -> Result<String> {
     let (source, _script_path, cmd_args) = parse_script_args(&args[2..])?;
     let output = if cmd_args
@@ -242,24 +396,51 @@ fn run_command<T: AsRef<OsStr>>(
     envs: HashMap<String, String>,
     cwd: Option<&Path>,
 ) -> Result<i32> {
-    let mut command = process::Command::new(prog);
-    command.args(args).envs(envs);
-    if let Some(cwd) = cwd {
-        command.current_dir(cwd);
-    }
     #[cfg(unix)]
     {
         use std::os::unix::process::CommandExt;
+        let mut command = process::Command::new(prog);
+        command.args(args).envs(envs);
+        if let Some(cwd) = cwd {
+            command.current_dir(cwd);
+        }
         let err = command.exec();
         bail!("Failed to run '{script_path}', {err}");
     }
     #[cfg(not(unix))]
     {
-        let status = command
-            .status()
-            .with_context(|| format!("Failed to run '{script_path}'"))?;
-        Ok(status.code().unwrap_or_default())
+        wait_command(script_path, prog, args, envs, cwd)
+    }
+}
+
+fn wait_command<T: AsRef<OsStr>>(
+    script_path: &str,
+    prog: &str,
+    args: &[T],
+    envs: HashMap<String, String>,
+    cwd: Option<&Path>,
+) -> Result<i32> {
+    let mut command = process::Command::new(prog);
+    command.args(args).envs(envs);
+    if let Some(cwd) = cwd {
+        command.current_dir(cwd);
+    }
+    let status = command
+        .status()
+        .with_context(|| format!("Failed to run '{script_path}'"))?;
+    Ok(exit_code(status))
+}
+
+/// Exit code of a finished process, `128 + signal` if it was killed by a signal like shells do
+fn exit_code(status: process::ExitStatus) -> i32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
     }
+    status.code().unwrap_or(1)
 }
 
 fn run_compgen(runtime: NativeRuntime, mut args: Vec<String>) -> Option<()> {
@@ -279,7 +460,7 @@ fn run_compgen(runtime: NativeRuntime, mut args: Vec<String>) -> Option<()> {
         }
     }
     let no_color = std::env::var("NO_COLOR")
-        .map(|v| is_true_value(&v))
+        .map(|v| !v.is_empty())
         .unwrap_or_default();
     let last_arg = args.last().map(|v| v.as_str()).unwrap_or_default();
     let output = if &args[4] == "argc" && (args[3].is_empty() || args[5].starts_with("--argc")) {
@@ -1,12 +1,59 @@
-use anyhow::Result;
-use argc::{NativeRuntime, Runtime};
+use crate::process_group;
+
+use anyhow::{Context, Result};
+use argc::{utils::is_true_value, NativeRuntime, Runtime};
+use serde::Serialize;
 use std::collections::HashMap;
-use std::process::{self, Command};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{self, Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 use threadpool::ThreadPool;
 
 pub const PARALLEL_SYMBOL: &str = "___parallel___";
 
+#[derive(Debug, Serialize)]
+struct JobResult {
+    args: Vec<String>,
+    status: JobStatus,
+    exit: Option<i32>,
+    duration: f64,
+    #[serde(skip)]
+    stdout: String,
+    #[serde(skip)]
+    stderr: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum JobStatus {
+    Ok,
+    Failed,
+    Cancelled,
+    Skipped,
+}
+
+impl JobStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Ok => "ok",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+            JobStatus::Skipped => "skipped",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Report<'a> {
+    exit: i32,
+    duration: f64,
+    jobs: &'a [JobResult],
+}
+
 pub fn parallel(
     runtime: NativeRuntime,
     shell: &str,
@@ -15,8 +62,26 @@ pub fn parallel(
 ) -> Result<()> {
     let jobs = to_jobs(args);
     let jobs_len = jobs.len();
-    let pool = ThreadPool::new(num_cpus::get());
+    let max_jobs = runtime
+        .env_var("ARGC_PARALLEL_JOBS")
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or_else(num_cpus::get);
+    let fail_fast = runtime
+        .env_var("ARGC_PARALLEL_FAIL_FAST")
+        .map(|v| is_true_value(&v))
+        .unwrap_or_default();
+    let stream = runtime.env_var("ARGC_PARALLEL_OUTPUT").as_deref() == Some("stream");
+    let summary = runtime
+        .env_var("ARGC_PARALLEL_SUMMARY")
+        .map(|v| is_true_value(&v))
+        .unwrap_or_default();
+    let report_file = runtime.env_var("ARGC_PARALLEL_REPORT");
+    let start_time = Instant::now();
+    let pool = ThreadPool::new(max_jobs);
     let (tx, rx) = channel();
+    let running: Arc<Mutex<HashMap<usize, Child>>> = Default::default();
+    let cancelled = Arc::new(AtomicBool::new(false));
     let path_env = runtime.path_env_with_current_exe();
     let mut shell_extra_args = runtime.shell_args(shell);
     shell_extra_args.push(script_file.to_string());
@@ -26,46 +91,171 @@ pub fn parallel(
         let shell = shell.to_string();
         let path_env = path_env.clone();
         let shell_extra_args = shell_extra_args.clone();
+        let running = running.clone();
+        let cancelled = cancelled.clone();
         pool.execute(move || {
-            Command::new(shell)
-                .args(shell_extra_args)
-                .args(job_args)
-                .env("ARGC_PARALLEL", "1")
-                .env("PATH", path_env)
-                .output()
-                .ok()
-                .and_then(|output| tx.send((i, output)).ok());
+            let mut result = JobResult {
+                args: job_args.clone(),
+                status: JobStatus::Skipped,
+                exit: None,
+                duration: 0.0,
+                stdout: String::new(),
+                stderr: String::new(),
+            };
+            if cancelled.load(Ordering::SeqCst) {
+                let _ = tx.send((i, result));
+                return;
+            }
+            let job_start_time = Instant::now();
+            let Ok(mut child) = process_group::spawn(
+                Command::new(shell)
+                    .args(shell_extra_args)
+                    .args(&job_args)
+                    .env("ARGC_PARALLEL", "1")
+                    .env("PATH", path_env)
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped()),
+            ) else {
+                result.status = JobStatus::Failed;
+                result.exit = Some(1);
+                let _ = tx.send((i, result));
+                return;
+            };
+            let prefix = stream.then(|| job_args[0].clone());
+            let stdout = child
+                .stdout
+                .take()
+                .map(|v| read_output(v, prefix.clone(), false));
+            let stderr = child.stderr.take().map(|v| read_output(v, prefix, true));
+            {
+                // Under the lock, fail-fast either sees the job or has already cancelled it
+                let mut running = running.lock().unwrap();
+                if cancelled.load(Ordering::SeqCst) {
+                    process_group::kill(&mut child);
+                }
+                running.insert(i, child);
+            }
+            let status = loop {
+                let status = running
+                    .lock()
+                    .unwrap()
+                    .get_mut(&i)
+                    .and_then(|v| v.try_wait().ok().flatten());
+                if let Some(status) = status {
+                    break status;
+                }
+                thread::sleep(Duration::from_millis(10));
+            };
+            if let Some(child) = running.lock().unwrap().remove(&i) {
+                process_group::release(&child);
+            }
+            result.duration = job_start_time.elapsed().as_secs_f64();
+            let code = crate::exit_code(status);
+            result.exit = Some(code);
+            result.status = match code {
+                0 => JobStatus::Ok,
+                _ if status.code().is_none() && cancelled.load(Ordering::SeqCst) => {
+                    JobStatus::Cancelled
+                }
+                _ => JobStatus::Failed,
+            };
+            // Processes spawned by a cancelled job may still hold its pipes, so leave the readers behind
+            let join = |v: Option<JoinHandle<String>>| match result.status {
+                JobStatus::Cancelled => String::new(),
+                _ => v.and_then(|v| v.join().ok()).unwrap_or_default(),
+            };
+            result.stdout = join(stdout);
+            result.stderr = join(stderr);
+            let _ = tx.send((i, result));
         });
     }
-    pool.join();
     drop(tx);
-    let mut job_outputs = HashMap::new();
+    let mut job_results = HashMap::new();
     let mut exit = 0;
-    for (i, job_output) in rx {
-        if !job_output.status.success() {
-            exit = 1;
+    for (i, result) in rx {
+        if result.status == JobStatus::Failed && exit == 0 {
+            exit = result.exit.unwrap_or(1);
+            if fail_fast {
+                let mut running = running.lock().unwrap();
+                cancelled.store(true, Ordering::SeqCst);
+                for child in running.values_mut() {
+                    process_group::kill(child);
+                }
+            }
         }
-        job_outputs.insert(
-            i,
-            (
-                String::from_utf8_lossy(&job_output.stdout).to_string(),
-                String::from_utf8_lossy(&job_output.stderr).to_string(),
-            ),
-        );
+        job_results.insert(i, result);
     }
-    for i in 0..jobs_len {
-        if let Some((stdout, stderr)) = job_outputs.get(&i) {
-            if !stdout.is_empty() {
-                print!("{stdout}")
-            }
-            if !stderr.is_empty() {
-                eprint!("{stderr}")
-            }
+    pool.join();
+    let job_results: Vec<JobResult> = (0..jobs_len)
+        .filter_map(|i| job_results.remove(&i))
+        .collect();
+    for result in &job_results {
+        if !result.stdout.is_empty() {
+            print!("{}", result.stdout)
         }
+        if !result.stderr.is_empty() {
+            eprint!("{}", result.stderr)
+        }
+    }
+    if summary {
+        print_summary(&job_results);
+    }
+    if let Some(report_file) = report_file {
+        let report = Report {
+            exit,
+            duration: start_time.elapsed().as_secs_f64(),
+            jobs: &job_results,
+        };
+        std::fs::write(&report_file, serde_json::to_string_pretty(&report)?)
+            .with_context(|| format!("Failed to write report to '{report_file}'"))?;
     }
     process::exit(exit)
 }
 
+fn print_summary(job_results: &[JobResult]) {
+    eprintln!("==== parallel summary ====");
+    for result in job_results {
+        let status = match (result.status, result.exit) {
+            (JobStatus::Failed, Some(code)) => format!("exit {code}"),
+            (status, _) => status.as_str().to_string(),
+        };
+        let duration = match result.status {
+            JobStatus::Skipped => "-".to_string(),
+            _ => format!("{:.2}s", result.duration),
+        };
+        eprintln!("{status:<10}{duration:>8}  {}", result.args.join(" "));
+    }
+}
+
+/// Read the output of a job, either printing it line by line with a `[prefix]`
+/// as soon as it arrives or collecting it to be printed once the job ends.
+fn read_output<R: Read + Send + 'static>(
+    reader: R,
+    prefix: Option<String>,
+    is_stderr: bool,
+) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        match prefix {
+            Some(prefix) => {
+                for line in reader.lines().map_while(Result::ok) {
+                    if is_stderr {
+                        eprintln!("[{prefix}] {line}");
+                    } else {
+                        println!("[{prefix}] {line}");
+                    }
+                }
+                String::new()
+            }
+            None => {
+                let mut output = vec![];
+                let _ = reader.read_to_end(&mut output);
+                String::from_utf8_lossy(&output).to_string()
+            }
+        }
+    })
+}
+
 fn to_jobs(args: &[String]) -> Vec<Vec<String>> {
     let mut jobs = Vec::new();
     let mut current = vec![];
@@ -0,0 +1,109 @@
+//! Jobs run in their own process groups, so that stopping a job also stops the processes it spawned.
+//!
+//! A job outside the foreground process group of the terminal is stopped by `SIGTTIN` as soon as it
+//! reads from the terminal, so jobs get a null stdin, and the signals the terminal sends to argc are
+//! forwarded to them.
+
+use std::io;
+use std::process::{Child, Command, Stdio};
+
+#[cfg(unix)]
+use std::sync::{
+    atomic::{AtomicI32, Ordering},
+    Once,
+};
+
+/// Most jobs whose signals are forwarded at once, the others only get the signals sent to their group
+#[cfg(unix)]
+const MAX_GROUPS: usize = 256;
+
+/// Slot of a group being spawned
+#[cfg(unix)]
+const RESERVED: i32 = -1;
+
+/// Process groups of the running jobs, 0 for a free slot, read lock-free by the signal handler
+#[cfg(unix)]
+static GROUPS: [AtomicI32; MAX_GROUPS] = [const { AtomicI32::new(0) }; MAX_GROUPS];
+
+/// Spawn the command as the leader of a new process group
+pub fn spawn(command: &mut Command) -> io::Result<Child> {
+    command.stdin(Stdio::null());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        forward_signals();
+        let slot = GROUPS.iter().find(|v| {
+            v.compare_exchange(0, RESERVED, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+        });
+        match command.process_group(0).spawn() {
+            Ok(child) => {
+                if let Some(slot) = slot {
+                    slot.store(child.id() as i32, Ordering::SeqCst);
+                }
+                Ok(child)
+            }
+            Err(err) => {
+                if let Some(slot) = slot {
+                    slot.store(0, Ordering::SeqCst);
+                }
+                Err(err)
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    command.spawn()
+}
+
+/// Kill the job and the processes in its group
+pub fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    unsafe {
+        libc::kill(-(child.id() as i32), libc::SIGKILL);
+    }
+    #[cfg(not(unix))]
+    let _ = child.kill();
+}
+
+/// Forget the group of a job that has been waited for
+pub fn release(child: &Child) {
+    #[cfg(unix)]
+    {
+        let pgid = child.id() as i32;
+        for slot in GROUPS.iter() {
+            let _ = slot.compare_exchange(pgid, 0, Ordering::SeqCst, Ordering::SeqCst);
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = child;
+}
+
+/// The jobs are no longer in the foreground process group, pass them the signals argc receives
+#[cfg(unix)]
+fn forward_signals() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        for signal in [libc::SIGINT, libc::SIGTERM, libc::SIGHUP] {
+            unsafe {
+                libc::signal(signal, on_signal as *const () as libc::sighandler_t);
+            }
+        }
+    });
+}
+
+/// Only atomic loads, `kill`, `signal` and `raise` here, which are all async-signal-safe
+#[cfg(unix)]
+extern "C" fn on_signal(signal: libc::c_int) {
+    for slot in GROUPS.iter() {
+        let pgid = slot.load(Ordering::SeqCst);
+        if pgid > 0 {
+            unsafe {
+                libc::kill(-pgid, signal);
+            }
+        }
+    }
+    unsafe {
+        libc::signal(signal, libc::SIG_DFL);
+        libc::raise(signal);
+    }
+}
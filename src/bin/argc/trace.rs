@@ -0,0 +1,104 @@
+use anyhow::{Context, Result};
+use argc::{utils::is_true_value, ArgcValue, NativeRuntime, Runtime};
+use serde_json::{json, Map, Value};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::time::Instant;
+
+const TRACE_DEPTH_ENV: &str = "ARGC_TRACE_DEPTH";
+
+pub struct Trace {
+    recipe: String,
+    args: Vec<String>,
+    values: Map<String, Value>,
+    depth: usize,
+    file: Option<String>,
+    start_time: Instant,
+}
+
+impl Trace {
+    /// Whether `ARGC_TRACE` or `ARGC_TRACE_FILE` is set
+    pub fn is_enabled(runtime: NativeRuntime) -> bool {
+        runtime.env_var("ARGC_TRACE_FILE").is_some()
+            || runtime
+                .env_var("ARGC_TRACE")
+                .map(|v| is_true_value(&v))
+                .unwrap_or_default()
+    }
+
+    /// Start tracing the recipe selected by the args if tracing is enabled
+    pub fn new(runtime: NativeRuntime, args: &[String], values: &[ArgcValue]) -> Option<Self> {
+        if !Self::is_enabled(runtime) {
+            return None;
+        }
+        let file = runtime.env_var("ARGC_TRACE_FILE");
+        let depth = runtime
+            .env_var(TRACE_DEPTH_ENV)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default();
+        let recipe = values.iter().find_map(|v| match v {
+            ArgcValue::CommandFn(name) | ArgcValue::UpToDate((name, _)) => Some(name.clone()),
+            _ => None,
+        })?;
+        Some(Self {
+            recipe,
+            args: args[1..].to_vec(),
+            values: to_json_values(values),
+            depth,
+            file,
+            start_time: Instant::now(),
+        })
+    }
+
+    /// Environment variable passed to the recipe so that nested recipes know their depth
+    pub fn env(&self) -> (String, String) {
+        (TRACE_DEPTH_ENV.to_string(), (self.depth + 1).to_string())
+    }
+
+    pub fn finish(self, code: i32) -> Result<()> {
+        let record = json!({
+            "recipe": self.recipe,
+            "args": self.args,
+            "values": self.values,
+            "depth": self.depth,
+            "exit": code,
+            "duration": self.start_time.elapsed().as_secs_f64(),
+        });
+        let line = format!("{record}\n");
+        match self.file {
+            Some(file) => {
+                let mut file_handle = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&file)
+                    .with_context(|| format!("Failed to open trace file '{file}'"))?;
+                file_handle
+                    .write_all(line.as_bytes())
+                    .with_context(|| format!("Failed to write trace file '{file}'"))?;
+            }
+            None => eprint!("{line}"),
+        }
+        Ok(())
+    }
+}
+
+fn to_json_values(values: &[ArgcValue]) -> Map<String, Value> {
+    let mut output = Map::new();
+    for value in values {
+        match value {
+            ArgcValue::Single(id, value) | ArgcValue::PositionalSingle(id, value) => {
+                output.insert(id.clone(), json!(value));
+            }
+            ArgcValue::Multiple(id, values) | ArgcValue::PositionalMultiple(id, values) => {
+                output.insert(id.clone(), json!(values));
+            }
+            ArgcValue::Map(id, map) => {
+                output.insert(id.clone(), json!(map));
+            }
+            _ => {}
+        }
+    }
+    // Drop the built-in variables such as `argc__args`
+    output.retain(|k, _| !k.starts_with('_'));
+    output
+}
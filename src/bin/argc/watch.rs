@@ -0,0 +1,101 @@
+use crate::process_group;
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(200);
+
+type Snapshot = HashMap<PathBuf, (Option<SystemTime>, u64)>;
+
+pub fn watch(
+    shell: &str,
+    script_file: &str,
+    script_dir: &Path,
+    paths: &[String],
+    args: &[String],
+    envs: HashMap<String, String>,
+) -> Result<()> {
+    let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    let spawn = |run: usize| -> Result<Child> {
+        eprintln!("==== [{run}] argc {} ====", args.join(" "));
+        process_group::spawn(
+            Command::new(shell)
+                .arg(script_file)
+                .args(args)
+                .envs(&envs)
+                .current_dir(script_dir),
+        )
+        .with_context(|| format!("Failed to run '{script_file}'"))
+    };
+    let mut snapshot = take_snapshot(&paths);
+    let mut run = 1;
+    let mut child = Some(spawn(run)?);
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let mut current = take_snapshot(&paths);
+        if current == snapshot {
+            if let Some(status) = child.as_mut().and_then(|v| v.try_wait().ok().flatten()) {
+                if let Some(child) = &child {
+                    process_group::release(child);
+                }
+                eprintln!(
+                    "==== exit {}, waiting for changes ====",
+                    crate::exit_code(status)
+                );
+                child = None;
+            }
+            continue;
+        }
+        // Wait for the changes to settle down
+        loop {
+            thread::sleep(DEBOUNCE_DELAY);
+            let next = take_snapshot(&paths);
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        snapshot = current;
+        if let Some(mut child) = child.take() {
+            process_group::kill(&mut child);
+            let _ = child.wait();
+            process_group::release(&child);
+        }
+        run += 1;
+        child = Some(spawn(run)?);
+    }
+}
+
+fn take_snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = HashMap::new();
+    for path in paths {
+        walk(path, &mut snapshot);
+    }
+    snapshot
+}
+
+fn walk(path: &Path, snapshot: &mut Snapshot) {
+    let Ok(meta) = fs::metadata(path) else {
+        return;
+    };
+    if meta.is_dir() {
+        let Ok(entries) = fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            // Skip hidden entries such as `.git`
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            walk(&entry.path(), snapshot);
+        }
+    } else {
+        snapshot.insert(path.to_path_buf(), (meta.modified().ok(), meta.len()));
+    }
+}
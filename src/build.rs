@@ -1,13 +1,17 @@
 use crate::{
-    command::Command,
-    param::{FlagOptionParam, Param, PositionalParam},
-    utils::{escape_shell_words, ARGC_LOAD_DOTENV, ARGC_REQUIRE_TOOLS},
+    command::{Command, ParamGroupKind},
+    locale::{format_message, Messages},
+    param::{FlagOptionParam, Param, ParamData, PositionalParam},
+    utils::{
+        escape_shell_words, render_deprecated, ARGC_LOAD_DOTENV, ARGC_REQUIRE_TOOLS,
+        ARGC_UP_TO_DATE, STYLE_HEADING, STYLE_LITERAL, STYLE_PLACEHOLDER, STYLE_RESET,
+    },
     ChoiceValue, DefaultValue,
 };
 use anyhow::Result;
 use indexmap::IndexSet;
 
-const UTIL_FNS: [(&str, &str); 7] = [
+const UTIL_FNS: [(&str, &str); 14] = [
     (
         "_argc_take_args",
         r#"
@@ -38,7 +42,7 @@ _argc_take_args() {
         done
     fi
     if [[ "${#_argc_take_args_values[@]}" -lt "$min" ]]; then
-        _argc_die "error: incorrect number of values for \`$param\`"
+        _argc_die "{mismatch_values}"
     fi
     if [[ -n "$delimiter" ]] && [[ "${#_argc_take_args_values[@]}" -gt 0 ]]; then
         local item values arr=()
@@ -93,7 +97,7 @@ _argc_match_positionals() {
     _argc_match_positionals_len=${#_argc_match_positionals_values[@]}
     if [[ $params_len -gt 0 ]] && [[ $_argc_match_positionals_len -gt $params_len ]]; then
         local index="${_argc_match_positionals_values[params_len]%%:*}"
-        _argc_die "error: unexpected argument \`${argc__positionals[index]}\` found"
+        _argc_die "{unknown_argument}"
     fi
 }
 "#,
@@ -136,6 +140,40 @@ _argc_require_params() {
         _argc_die "$message$missed_envs"
     fi
 }
+"#,
+    ),
+    (
+        "_argc_validate_conflicts",
+        r#"
+_argc_validate_conflicts() {
+    local item name render_name used_name=""
+    for item in "$@"; do
+        name="${item%%:*}"
+        render_name="${item#*:}"
+        if [[ -n "${!name:-}" ]]; then
+            if [[ -n "$used_name" ]]; then
+                _argc_die "{conflict_arguments}"
+            fi
+            used_name="$render_name"
+        fi
+    done
+}
+"#,
+    ),
+    (
+        "_argc_require_one_of",
+        r#"
+_argc_require_one_of() {
+    local item name render_names=""
+    for item in "$@"; do
+        name="${item%%:*}"
+        if [[ -n "${!name:-}" ]]; then
+            return
+        fi
+        render_names="$render_names"$'\n'"  ${item#*:}"
+    done
+    _argc_die "{missing_one_of_arguments}$render_names"
+}
 "#,
     ),
     (
@@ -161,7 +199,80 @@ _argc_validate_choices() {
             fi
         done
         if [[ $pass -ne 1 ]]; then
-            _argc_die "error: invalid value \`$item\` for $render_name"$'\n'"  [possible values: $concated_choices]"
+            _argc_die "{invalid_value}"$'\n'"  {possible_values}$(_argc_did_you_mean "$item" "${choices[@]}")"
+        fi
+    done
+}
+"#,
+    ),
+    (
+        "_argc_validate_type",
+        r#"
+_argc_validate_type() {
+    local render_name="$1" value_type="$2" item pattern expected pass
+    case "$value_type" in
+    int) expected="integer" pattern='^[+-]?[0-9]+$' ;;
+    float) expected="number" pattern='^[+-]?([0-9]+(\.[0-9]*)?|\.[0-9]+)([eE][+-]?[0-9]+)?$' ;;
+    bool) expected="boolean" pattern='^(true|false|0|1)$' ;;
+    path) expected="existing path" ;;
+    file) expected="existing file" ;;
+    dir) expected="existing directory" ;;
+    url) expected="url" pattern='^[A-Za-z][A-Za-z0-9+.-]*://[^[:space:]]+$' ;;
+    regex) expected="regular expression" ;;
+    esac
+    for item in "${@:3}"; do
+        pass=1
+        case "$value_type" in
+        path) [[ -e "$item" ]] || pass=0 ;;
+        file) [[ -f "$item" ]] || pass=0 ;;
+        dir) [[ -d "$item" ]] || pass=0 ;;
+        regex) [[ "" =~ $item ]] || [[ $? -ne 2 ]] || pass=0 ;;
+        *) [[ "$item" =~ $pattern ]] || pass=0 ;;
+        esac
+        if [[ $pass -ne 1 ]]; then
+            _argc_die "{invalid_value}"$'\n'"  {expected}"
+        fi
+    done
+}
+"#,
+    ),
+    (
+        "_argc_validate_range",
+        r#"
+_argc_validate_range() {
+    local render_name="$1" kind="$2" min="$3" max="$4" inclusive="$5" item value
+    local render_range="$min..${inclusive:+=}$max"
+    local pattern='^[+-]?([0-9]+(\.[0-9]*)?|\.[0-9]+)([eE][+-]?[0-9]+)?$'
+    for item in "${@:6}"; do
+        if [[ "$kind" == "length" ]]; then
+            value="${#item}"
+        elif [[ "$item" =~ $pattern ]]; then
+            value="$item"
+        else
+            _argc_die "{invalid_value}"$'\n'"  {expected_number}"
+        fi
+        if ! awk -v v="$value" -v min="$min" -v max="$max" -v inclusive="$inclusive" 'BEGIN {
+            if (min != "" && v + 0 < min + 0) exit 1
+            if (max != "" && inclusive != "" && v + 0 > max + 0) exit 1
+            if (max != "" && inclusive == "" && v + 0 >= max + 0) exit 1
+        }'; then
+            if [[ "$kind" == "length" ]]; then
+                _argc_die "{invalid_value}"$'\n'"  {length}"
+            fi
+            _argc_die "{invalid_value}"$'\n'"  {range}"
+        fi
+    done
+}
+"#,
+    ),
+    (
+        "_argc_validate_pattern",
+        r#"
+_argc_validate_pattern() {
+    local render_name="$1" pattern="$2" render_pattern="$3" item
+    for item in "${@:4}"; do
+        if ! [[ "$item" =~ $pattern ]]; then
+            _argc_die "{invalid_value}"$'\n'"  {pattern}"
         fi
     done
 }
@@ -178,7 +289,7 @@ _argc_check_bool() {
     elif [[ "$env_value" == "false" ]] || [[ "$env_value" == "0" ]]; then
         return 1
     else
-        _argc_die "error: environment variable '$env_name' has invalid value for param '$param_name'"
+        _argc_die "{invalid_bind_environment}"
     fi
 }
 "#,
@@ -212,6 +323,80 @@ _argc_maybe_flag_option() {
 }
 "#,
     ),
+    (
+        "_argc_did_you_mean",
+        r#"
+_argc_did_you_mean() {
+    local value="$1" value_len="${#1}" suggestion="" best=-1 candidate
+    shift
+    for candidate in "$@"; do
+        local candidate_len="${#candidate}" width=$((${#candidate} + 1)) i j cost min distance max_len
+        local -a d=()
+        for ((i = 0; i <= value_len; i++)); do
+            d[i * width]=$i
+        done
+        for ((j = 0; j <= candidate_len; j++)); do
+            d[j]=$j
+        done
+        for ((i = 1; i <= value_len; i++)); do
+            for ((j = 1; j <= candidate_len; j++)); do
+                cost=1
+                if [[ "${value:i-1:1}" == "${candidate:j-1:1}" ]]; then
+                    cost=0
+                fi
+                min=$((d[(i - 1) * width + j] + 1))
+                if [[ $((d[i * width + j - 1] + 1)) -lt $min ]]; then
+                    min=$((d[i * width + j - 1] + 1))
+                fi
+                if [[ $((d[(i - 1) * width + j - 1] + cost)) -lt $min ]]; then
+                    min=$((d[(i - 1) * width + j - 1] + cost))
+                fi
+                if [[ $i -gt 1 ]] && [[ $j -gt 1 ]] && [[ "${value:i-1:1}" == "${candidate:j-2:1}" ]] && [[ "${value:i-2:1}" == "${candidate:j-1:1}" ]]; then
+                    if [[ $((d[(i - 2) * width + j - 2] + 1)) -lt $min ]]; then
+                        min=$((d[(i - 2) * width + j - 2] + 1))
+                    fi
+                fi
+                d[i * width + j]=$min
+            done
+        done
+        distance="${d[value_len * width + candidate_len]}"
+        max_len=$value_len
+        if [[ $candidate_len -gt $max_len ]]; then
+            max_len=$candidate_len
+        fi
+        if [[ $((distance * 3)) -le $max_len ]] && { [[ $best -lt 0 ]] || [[ $distance -lt $best ]]; }; then
+            best=$distance
+            suggestion="$candidate"
+        fi
+    done
+    if [[ -n "$suggestion" ]]; then
+        printf "\n  {did_you_mean}" "$suggestion"
+    fi
+}
+"#,
+    ),
+    (
+        "_argc_help_styles",
+        r#"
+_argc_help_styles() {
+    _argc_style_heading= _argc_style_literal= _argc_style_placeholder= _argc_style_reset=
+    if [[ -t 1 ]] && [[ -z "${NO_COLOR:-}" ]]; then
+        _argc_style_heading=$'\e[1;32m'
+        _argc_style_literal=$'\e[1;36m'
+        _argc_style_placeholder=$'\e[36m'
+        _argc_style_reset=$'\e[0m'
+    fi
+}
+"#,
+    ),
+];
+
+/// Variables set by `_argc_help_styles` for the ANSI styles of the help
+const HELP_STYLE_VARS: [(&str, &str); 4] = [
+    (STYLE_HEADING, "${_argc_style_heading}"),
+    (STYLE_LITERAL, "${_argc_style_literal}"),
+    (STYLE_PLACEHOLDER, "${_argc_style_placeholder}"),
+    (STYLE_RESET, "${_argc_style_reset}"),
 ];
 
 pub fn build(source: &str, root_name: &str, wrap_width: Option<usize>) -> Result<String> {
@@ -261,12 +446,50 @@ fn build_root(cmd: &Command, wrap_width: Option<usize>) -> String {
             util_fns.push_str(util_fn);
         }
     }
+    let mut util_fns = localize_util_fns(&util_fns, cmd.locale().messages());
     let dotenv = if let Some(value) = cmd.dotenv() {
         util_fns.push_str(&format!("\n{ARGC_LOAD_DOTENV}\n"));
         format!("\n    _argc_load_dotenv {}", escape_shell_words(value))
     } else {
         String::new()
     };
+    let (init_warnings, warnings) = if command.contains("_argc_warnings") {
+        (
+            "\n    _argc_warnings=()",
+            r#"
+    if [[ "${#_argc_warnings[@]}" -gt 0 ]]; then
+        printf "%s\n" "${_argc_warnings[@]}" >&2
+    fi"#,
+        )
+    } else {
+        ("", "")
+    };
+    let (init_deps, deps) = if command.contains("_argc_deps") {
+        (
+            "\n    _argc_deps=()",
+            r#"
+        for _argc_dep in "${_argc_deps[@]}"; do
+            $_argc_dep || exit $?
+        done"#,
+        )
+    } else {
+        ("", "")
+    };
+    if command.contains("_argc_up_to_date") {
+        util_fns.push_str(&format!("\n{ARGC_UP_TO_DATE}\n"));
+    }
+    let (init_up_to_date, up_to_date) = if command.contains("_argc_up_to_date_args") {
+        (
+            "\n    _argc_up_to_date_args=()",
+            r#"
+        if [[ "${#_argc_up_to_date_args[@]}" -gt 0 ]] && _argc_up_to_date "${_argc_up_to_date_args[@]:1}"; then
+            echo "${_argc_up_to_date_args[0]}" >&2
+            exit 0
+        fi"#,
+        )
+    } else {
+        ("", "")
+    };
     let require_tools = if command.contains("_argc_tools") {
         util_fns.push_str(&format!("\n{ARGC_REQUIRE_TOOLS}\n"));
         r#"
@@ -291,9 +514,9 @@ _argc_run() {{
     argc__positionals=()
     _argc_index=1
     _argc_len="${{#argc__args[@]}}"{dotenv}
-    _argc_tools=()
-    _argc_parse{require_tools}{before_hook}
-    if [ -n "${{argc__fn:-}}" ]; then
+    _argc_tools=(){init_deps}{init_up_to_date}{init_warnings}
+    _argc_parse{warnings}{require_tools}{before_hook}
+    if [ -n "${{argc__fn:-}}" ]; then{deps}{up_to_date}
         $argc__fn "${{argc__positionals[@]}}"{after_hook}
     fi
 }}
@@ -313,6 +536,98 @@ _argc_run "$@"
     )
 }
 
+/// Fill the `{name}` placeholders of the util functions with the messages of the script locale
+fn localize_util_fns(util_fns: &str, messages: &Messages) -> String {
+    let value = [("value", "$item"), ("target", "$render_name")];
+    let args = [
+        (
+            "mismatch_values",
+            build_message(messages.mismatch_values, &[("name", "$param")]),
+        ),
+        (
+            "unknown_argument",
+            build_message(
+                messages.unknown_argument,
+                &[("name", "${argc__positionals[index]}")],
+            ),
+        ),
+        (
+            "conflict_arguments",
+            build_message(
+                messages.conflict_arguments,
+                &[("name", "$used_name"), ("other", "$render_name")],
+            ),
+        ),
+        (
+            "missing_one_of_arguments",
+            build_message(messages.missing_one_of_arguments, &[]),
+        ),
+        (
+            "invalid_value",
+            build_message(messages.invalid_value, &value),
+        ),
+        (
+            "possible_values",
+            build_message(messages.possible_values, &[("values", "$concated_choices")]),
+        ),
+        (
+            "expected",
+            build_message(messages.expected, &[("expected", "$expected")]),
+        ),
+        (
+            "expected_number",
+            build_message(messages.expected, &[("expected", "number")]),
+        ),
+        (
+            "range",
+            build_message(messages.range, &[("range", "$render_range")]),
+        ),
+        (
+            "length",
+            build_message(messages.length, &[("length", "$render_range")]),
+        ),
+        (
+            "pattern",
+            build_message(messages.pattern, &[("pattern", "$render_pattern")]),
+        ),
+        (
+            "invalid_bind_environment",
+            build_message(
+                messages.invalid_bind_environment,
+                &[("env", "$env_name"), ("name", "$param_name")],
+            ),
+        ),
+        (
+            "did_you_mean",
+            build_message(messages.did_you_mean, &[("value", "%s")]),
+        ),
+    ];
+    let args: Vec<(&str, &str)> = args.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    format_message(util_fns, &args)
+}
+
+/// Render a message inside a double-quoted bash string, the args are bash expressions kept as is
+fn build_message(template: &str, args: &[(&str, &str)]) -> String {
+    let template = template
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "\\$")
+        .replace('`', "\\`");
+    format_message(&template, args)
+}
+
+/// Escape the help for an unquoted heredoc and swap its ANSI styles for `_argc_help_styles` variables
+fn build_styled_heredoc(help: &str) -> String {
+    let mut output = help
+        .replace('\\', "\\\\")
+        .replace('$', "\\$")
+        .replace('`', "\\`");
+    for (style, var) in HELP_STYLE_VARS {
+        output = output.replace(style, var);
+    }
+    output
+}
+
 fn build_command(cmd: &Command, wrap_width: Option<usize>) -> String {
     let suffix = if cmd.is_root() {
         String::new()
@@ -321,12 +636,13 @@ fn build_command(cmd: &Command, wrap_width: Option<usize>) -> String {
     };
 
     let usage = {
-        let usage = cmd.render_help(wrap_width);
-        let usage = usage.trim();
+        let usage = cmd.render_help(wrap_width, true, cmd.locale());
+        let usage = build_styled_heredoc(usage.trim());
         format!(
             r#"
 _argc_usage{suffix}() {{
-    cat <<-'EOF'
+    _argc_help_styles
+    cat <<-EOF
 {usage}
 EOF
     exit
@@ -361,6 +677,7 @@ _argc_version{suffix}() {{
 }
 
 fn build_parse(cmd: &Command, suffix: &str) -> String {
+    let messages = cmd.locale().messages();
     let mut parse_help = {
         let help_flags = cmd.help_flags.join(" | ");
         format!(
@@ -394,27 +711,60 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
         let parses: Vec<String> = cmd
             .flag_option_params
             .iter()
-            .map(|param| build_parse_flag_option(param, &flag_option_signs))
+            .map(|param| build_parse_flag_option(param, &flag_option_signs, messages))
             .collect();
         parses.join("")
     } else {
         String::new()
     };
+    let subcmd_names = cmd
+        .list_subcommand_names()
+        .iter()
+        .map(|v| escape_shell_words(v))
+        .collect::<Vec<String>>()
+        .join(" ");
     let parse_subcommands = if !cmd.subcommands.is_empty() {
         let mut parses: Vec<String> = cmd
             .subcommands
             .iter()
             .map(|subcmd| {
                 let paths = subcmd.paths.join("_");
-                let names = subcmd.list_names().join(" | ");
-                format!(
-                    r#"
-        {names})
+                let names: Vec<String> = subcmd
+                    .list_names()
+                    .into_iter()
+                    .filter(|v| !subcmd.deprecated_aliases.contains_key(v))
+                    .collect();
+                let parse = |names: &[String], deprecated: String| {
+                    let names = names.join(" | ");
+                    format!(
+                        r#"
+        {names}){deprecated}
             _argc_index=$((_argc_index + 1))
             _argc_action=_argc_parse_{paths}
             break
             ;;"#
-                )
+                    )
+                };
+                let deprecated = match &subcmd.deprecated {
+                    Some(message) => build_deprecated(
+                        messages,
+                        messages.command_target,
+                        &subcmd.cmd_name(),
+                        message,
+                        3,
+                    ),
+                    None => String::new(),
+                };
+                let mut output = parse(&names, deprecated.clone());
+                for (alias, message) in &subcmd.deprecated_aliases {
+                    let deprecated = if deprecated.is_empty() {
+                        build_deprecated(messages, messages.alias_target, alias, message, 3)
+                    } else {
+                        deprecated.clone()
+                    };
+                    output.push_str(&parse(std::slice::from_ref(alias), deprecated));
+                }
+                output
             })
             .collect();
 
@@ -434,6 +784,12 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
             .collect::<Vec<String>>()
             .join("");
 
+        let invalid_help_arg = build_message(
+            messages.invalid_value,
+            &[("value", "$help_arg"), ("target", "\\`<command>\\`")],
+        );
+        let possible_values =
+            build_message(messages.possible_values, &[("values", "$_argc_subcmds")]);
         parses.push(format!(
                 r#"
         help)
@@ -443,7 +799,7 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
                 _argc_usage{suffix}
                 ;;
             *)
-                _argc_die "error: invalid value \`$help_arg\` for \`<command>\`"$'\n'"  [possible values: $_argc_subcmds]"
+                _argc_die "{invalid_help_arg}"$'\n'"  {possible_values}$(_argc_did_you_mean "$help_arg" {subcmd_names})"
                 ;;
             esac
             ;;"#
@@ -455,10 +811,17 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
 
     let handle_unknown_flag_options = if !cmd.flag_option_params.is_empty() {
         let signs = flag_option_signs.iter().collect::<String>();
+        let names = cmd
+            .list_flag_option_names()
+            .iter()
+            .map(|v| escape_shell_words(v))
+            .collect::<Vec<String>>()
+            .join(" ");
+        let unknown_argument = build_message(messages.unknown_argument, &[("name", "$_argc_key")]);
         format!(
             r#"
             if _argc_maybe_flag_option "{signs}" "$_argc_item"; then
-                _argc_die "error: unexpected argument \`$_argc_key\` found"
+                _argc_die "{unknown_argument}$(_argc_did_you_mean "$_argc_key" {names})"
             fi"#,
         )
     } else {
@@ -478,10 +841,18 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
             ;;"#
             )
         } else {
+            let invalid_subcommand = build_message(
+                messages.invalid_subcommand,
+                &[("cmd", &name), ("arg", "$_argc_item")],
+            );
+            let subcommand_list = build_message(
+                messages.subcommand_list,
+                &[("subcommands", "$_argc_subcmds")],
+            );
             format!(
                 r#"
         *){handle_unknown_flag_options}
-            _argc_die "error: \`{name}\` requires a subcommand but one was not provided"$'\n'"  [subcommands: $_argc_subcmds]"
+            _argc_die "{invalid_subcommand}"$'\n'"  {subcommand_list}$(_argc_did_you_mean "$_argc_item" {subcmd_names})"
             ;;"#
             )
         }
@@ -509,6 +880,7 @@ fn build_parse(cmd: &Command, suffix: &str) -> String {
 
     let flag_option_bind_envs = build_flag_option_bind_envs(cmd);
     let required_flag_options = build_required_flag_options(cmd);
+    let param_groups = build_param_groups(cmd);
 
     let require_tools = build_require_tools(cmd);
     let handle = build_handle(cmd, suffix);
@@ -540,7 +912,7 @@ _argc_parse{suffix}() {{
         _argc_key="${{_argc_item%%=*}}"
         case "$_argc_key" in{combined_case}
         esac
-    done{flag_option_bind_envs}{required_flag_options}{require_tools}
+    done{flag_option_bind_envs}{required_flag_options}{param_groups}{require_tools}
     if [[ -n "${{_argc_action:-}}" ]]; then
         $_argc_action
     else{handle}
@@ -550,10 +922,21 @@ _argc_parse{suffix}() {{
     )
 }
 
-fn build_parse_flag_option(param: &FlagOptionParam, signs: &IndexSet<char>) -> String {
+fn build_parse_flag_option(
+    param: &FlagOptionParam,
+    signs: &IndexSet<char>,
+    messages: &Messages,
+) -> String {
     let names = param.list_names().join(" | ");
     let long_name = param.long_name();
+    let not_multiple = build_message(messages.not_multiple_argument, &[("name", &long_name)]);
     let var_name = param.var_name();
+    let deprecated = match param.deprecated() {
+        Some(message) => {
+            build_deprecated(messages, messages.argument_target, &long_name, message, 3)
+        }
+        None => String::new(),
+    };
     if param.is_flag() {
         if param.id() == "help" || param.id() == "version" {
             return String::new();
@@ -561,16 +944,15 @@ fn build_parse_flag_option(param: &FlagOptionParam, signs: &IndexSet<char>) -> S
         let variant = if param.multiple_occurs() {
             format!("{var_name}=$(({var_name} + 1))")
         } else {
-            format!(
-                r#"_argc_die "error: the argument \`{long_name}\` cannot be used multiple times""#
-            )
+            format!(r#"_argc_die "{not_multiple}""#)
         };
+        let no_flag_value = build_message(messages.no_flag_value, &[("name", &long_name)]);
 
         format!(
             r#"
-        {names})
+        {names}){deprecated}
             if [[ "$_argc_item" == *=* ]]; then
-                _argc_die "error: flag \`{long_name}\` don't accept any value"
+                _argc_die "{no_flag_value}"
             fi
             _argc_index=$((_argc_index + 1))
             if [[ -n "${{{var_name}:-}}" ]]; then
@@ -598,6 +980,12 @@ fn build_parse_flag_option(param: &FlagOptionParam, signs: &IndexSet<char>) -> S
             param.choice(),
             3,
         );
+        let constraints = build_constraints(
+            "{_argc_take_args_values[@]}",
+            &format!("`{render_first_notation}`"),
+            param.data(),
+            3,
+        );
         let variant = if param.multiple_values() {
             format!(
                 r#"
@@ -609,16 +997,20 @@ fn build_parse_flag_option(param: &FlagOptionParam, signs: &IndexSet<char>) -> S
             if [[ -z "${{{var_name}:-}}" ]]; then
                 {var_name}="${{_argc_take_args_values[0]:-}}"
             else
-                _argc_die "error: the argument \`{long_name}\` cannot be used multiple times"
+                _argc_die "{not_multiple}"
             fi"#
             )
         };
         let (min, max) = param.num_args();
         let code = if param.assigned() {
             let not_assigned = if min == 1 {
+                let mismatch_values = build_message(
+                    messages.mismatch_values,
+                    &[("name", &render_name_notations)],
+                );
                 format!(
                     r#"
-                _argc_die "error: incorrect number of values for \`{render_name_notations}\`""#
+                _argc_die "{mismatch_values}""#
                 )
             } else {
                 r#"
@@ -641,8 +1033,8 @@ fn build_parse_flag_option(param: &FlagOptionParam, signs: &IndexSet<char>) -> S
         };
         format!(
             r#"
-        {names}){code}
-            _argc_index=$((_argc_index + _argc_take_args_len + 1)){choice}{variant}
+        {names}){deprecated}{code}
+            _argc_index=$((_argc_index + _argc_take_args_len + 1)){choice}{constraints}{variant}
             ;;"#
         )
     }
@@ -658,7 +1050,8 @@ fn build_handle(cmd: &Command, suffix: &str) -> String {
     let set_argc_fn = match &cmd.command_fn {
         Some(fn_name) => format!(
             r#"
-        argc__fn={fn_name}"#
+        argc__fn={fn_name}{}"#,
+            build_deps(cmd)
         ),
         None => String::new(),
     };
@@ -676,8 +1069,11 @@ fn build_handle(cmd: &Command, suffix: &str) -> String {
 
     let envs = build_envs(cmd);
 
-    let output =
-        format!("{set_argc_fn}{run_help}{positionals}{default_flag_options}{required_envs}{envs}");
+    let up_to_date = build_up_to_date(cmd);
+
+    let output = format!(
+        "{set_argc_fn}{run_help}{positionals}{default_flag_options}{required_envs}{envs}{up_to_date}"
+    );
     if output.is_empty() {
         r#"
         :;"#
@@ -703,10 +1099,68 @@ fn build_require_tools(cmd: &Command) -> String {
     )
 }
 
+fn build_up_to_date(cmd: &Command) -> String {
+    let targets = cmd.meta_targets();
+    if targets.is_empty() {
+        return String::new();
+    }
+    let var_name = match cmd
+        .flag_option_params
+        .iter()
+        .find(|param| param.is_force_override())
+    {
+        Some(param) => param.var_name(),
+        None => return String::new(),
+    };
+    let targets = targets
+        .iter()
+        .map(|v| escape_shell_words(v))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let sources = cmd
+        .meta_sources()
+        .iter()
+        .map(|v| escape_shell_words(v))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let name = cmd.cmd_name();
+    let messages = cmd.locale().messages();
+    if !cmd.dep_stages.is_empty() {
+        // Checked by `_argc_run` once the dependencies have run, the message goes first
+        let message = escape_shell_words(&format_message(messages.up_to_date, &[("name", &name)]));
+        return format!(
+            r#"
+        if [[ -z "${{{var_name}:-}}" ]]; then
+            _argc_up_to_date_args=({message} {targets} -- {sources})
+        fi"#
+        );
+    }
+    let up_to_date = build_message(messages.up_to_date, &[("name", &name)]);
+    format!(
+        r#"
+        if [[ -z "${{{var_name}:-}}" ]] && _argc_up_to_date {targets} -- {sources}; then
+            echo "{up_to_date}" >&2
+            exit 0
+        fi"#
+    )
+}
+
+fn build_deps(cmd: &Command) -> String {
+    if cmd.dep_stages.is_empty() {
+        return String::new();
+    }
+    let deps = cmd.dep_stages.concat().join(" ");
+    format!(
+        r#"
+        _argc_deps=({deps})"#
+    )
+}
+
 fn build_positionals(cmd: &Command) -> String {
     if cmd.positional_params.is_empty() {
         return String::new();
     }
+    let messages = cmd.locale().messages();
     let split_args = cmd
         .positional_params
         .iter()
@@ -751,6 +1205,12 @@ fn build_positionals(cmd: &Command) -> String {
                 param.choice(),
                 3,
             );
+            let constraints = build_constraints(
+                &choice_variable,
+                &format!("`{render_value}`"),
+                param.data(),
+                3,
+            );
             let default = if param.default().is_some() {
                 let default = build_default(&var_name, param.default(), 3);
                 format!(
@@ -762,15 +1222,16 @@ fn build_positionals(cmd: &Command) -> String {
             };
 
             let required = if param.required() {
+                let missing_required = build_message(messages.missing_required_arguments, &[]);
                 format!(
                     r#"
-            _argc_die "error: the required environments \`{render_value}\` were not provided""#
+            _argc_die "{missing_required}"$'\n'"  {render_value}""#
                 )
             } else {
                 String::new()
             };
 
-            let bind_env = build_positional_bind_env(param);
+            let bind_env = build_positional_bind_env(param, messages);
 
             let handle_nonexist = format!("{default}{required}");
             let handle_nonexist = if !handle_nonexist.is_empty() {
@@ -784,7 +1245,7 @@ fn build_positionals(cmd: &Command) -> String {
             format!(
                 r#"
         IFS=: read -r values_index values_size <<<"${{_argc_match_positionals_values[{index}]:-}}"
-        if [[ -n "$values_index" ]]; then{variant}{choice}{bind_env}{handle_nonexist}
+        if [[ -n "$values_index" ]]; then{variant}{choice}{constraints}{bind_env}{handle_nonexist}
         fi"#
             )
         })
@@ -798,6 +1259,7 @@ fn build_positionals(cmd: &Command) -> String {
 }
 
 fn build_flag_option_bind_envs(cmd: &Command) -> String {
+    let messages = cmd.locale().messages();
     let mut output = vec![];
     for param in &cmd.flag_option_params {
         if let Some(env_name) = param.bind_env() {
@@ -813,7 +1275,7 @@ fn build_flag_option_bind_envs(cmd: &Command) -> String {
     fi"#
                 )
             } else {
-                let handle_bind_env = build_handle_bind_env(param, &render_name, 2);
+                let handle_bind_env = build_handle_bind_env(param, &render_name, 2, messages);
                 format!(
                     r#"
     if [[ -z "${{{var_name}:-}}" ]] && [[ -n "${{{env_name}:-}}" ]]; then{handle_bind_env}
@@ -826,11 +1288,12 @@ fn build_flag_option_bind_envs(cmd: &Command) -> String {
     output.join("")
 }
 
-fn build_positional_bind_env(param: &PositionalParam) -> String {
+fn build_positional_bind_env(param: &PositionalParam, messages: &Messages) -> String {
     match param.bind_env() {
         None => String::new(),
         Some(env_name) => {
-            let handle_bind_env = build_handle_bind_env(param, &param.render_notation(), 3);
+            let handle_bind_env =
+                build_handle_bind_env(param, &param.render_notation(), 3, messages);
             format!(
                 r#"
         elif [[ -n "${{{env_name}:-}}" ]]; then{handle_bind_env}
@@ -840,7 +1303,12 @@ fn build_positional_bind_env(param: &PositionalParam) -> String {
     }
 }
 
-fn build_handle_bind_env<T: Param>(param: &T, render_name: &str, indent_level: usize) -> String {
+fn build_handle_bind_env<T: Param>(
+    param: &T,
+    render_name: &str,
+    indent_level: usize,
+    messages: &Messages,
+) -> String {
     let indent = build_indent(indent_level);
     let env_name = param.bind_env().unwrap_or_default();
     let var_name = param.var_name();
@@ -855,12 +1323,18 @@ fn build_handle_bind_env<T: Param>(param: &T, render_name: &str, indent_level: u
         ),
     };
 
+    let target = format_message(
+        messages.bind_env_target,
+        &[("env", &env_name), ("name", render_name)],
+    );
     let choice = build_choice(
         "{_argc_env_values[@]}",
-        &format!(r#"environment variable `{env_name}` that bound to `{render_name}`"#),
+        &target,
         param.choice(),
         indent_level,
     );
+    let constraints =
+        build_constraints("{_argc_env_values[@]}", &target, param.data(), indent_level);
 
     let variant = if param.multiple_values() {
         format!(
@@ -873,7 +1347,7 @@ fn build_handle_bind_env<T: Param>(param: &T, render_name: &str, indent_level: u
 {indent}{var_name}="${{_argc_env_values[0]}}""#
         )
     };
-    format!(r#"{indent}{split_env}{choice}{variant}"#)
+    format!(r#"{indent}{split_env}{choice}{constraints}{variant}"#)
 }
 
 fn build_required_flag_options(cmd: &Command) -> String {
@@ -894,13 +1368,62 @@ fn build_required_flag_options(cmd: &Command) -> String {
         })
         .collect::<Vec<String>>()
         .join(" ");
+    let missing_required = build_message(cmd.locale().messages().missing_required_arguments, &[]);
     format!(
         r#"
-    _argc_require_params "error: the following required arguments were not provided:" \
+    _argc_require_params "{missing_required}" \
         {values}"#
     )
 }
 
+fn build_param_groups(cmd: &Command) -> String {
+    let messages = cmd.locale().messages();
+    let render_item = |id: &String| match cmd.find_flag_option(id) {
+        Some(param) => format!("'{}:{}'", param.var_name(), param.render_name_notations()),
+        None => String::new(),
+    };
+    let mut output = String::new();
+    for group in &cmd.param_groups {
+        let values: Vec<String> = group.ids.iter().map(render_item).collect();
+        match group.kind {
+            ParamGroupKind::Conflicts => {
+                let values = values.join(" ");
+                output.push_str(&format!(
+                    r#"
+    _argc_validate_conflicts {values}"#
+                ));
+            }
+            ParamGroupKind::Requires => {
+                let Some(param) = cmd.find_flag_option(&group.ids[0]) else {
+                    continue;
+                };
+                let var_name = param.var_name();
+                let render_name = param.render_name_notations();
+                let values = values[1..].join(" ");
+                let missing_dependent = build_message(
+                    messages.missing_dependent_arguments,
+                    &[("name", &render_name)],
+                );
+                output.push_str(&format!(
+                    r#"
+    if [[ -n "${{{var_name}:-}}" ]]; then
+        _argc_require_params "{missing_dependent}" \
+            {values}
+    fi"#
+                ));
+            }
+            ParamGroupKind::OneOfRequired => {
+                let values = values.join(" ");
+                output.push_str(&format!(
+                    r#"
+    _argc_require_one_of {values}"#
+                ));
+            }
+        }
+    }
+    output
+}
+
 fn build_default_flag_options(cmd: &Command) -> String {
     let default_flag_options: Vec<_> = cmd
         .flag_option_params
@@ -943,9 +1466,11 @@ fn build_required_envs(cmd: &Command) -> String {
         })
         .collect::<Vec<String>>()
         .join(" ");
+    let missing_required =
+        build_message(cmd.locale().messages().missing_required_environments, &[]);
     format!(
         r#"
-        _argc_require_params "error: the following required environments were not provided:" \
+        _argc_require_params "{missing_required}" \
             {values}"#
     )
 }
@@ -954,17 +1479,16 @@ fn build_envs(cmd: &Command) -> String {
     if cmd.env_params.is_empty() {
         return String::new();
     }
+    let messages = cmd.locale().messages();
     cmd.env_params
         .iter()
         .map(|param| {
             let var_name = param.var_name();
             let default = build_default(&format!("export {var_name}"), param.default(), 3);
-            let choice = build_choice(
-                &var_name,
-                &format!(r#"environment variable `{var_name}`"#),
-                param.choice(),
-                3,
-            );
+            let target = format_message(messages.env_target, &[("name", &var_name)]);
+            let choice = build_choice(&var_name, &target, param.choice(), 3);
+            let constraints = build_constraints(&var_name, &target, param.data(), 3);
+            let choice = format!("{choice}{constraints}");
             if default.is_empty() && choice.is_empty() {
                 String::new()
             } else if default.is_empty() {
@@ -1041,6 +1565,106 @@ fn build_choice(
     }
 }
 
+fn build_constraints(variable: &str, target_name: &str, data: &ParamData, indent: usize) -> String {
+    let indent = build_indent(indent);
+    let mut output = String::new();
+    if let Some(value_type) = &data.value_type {
+        let name = value_type.name();
+        output.push_str(&format!(
+            r#"
+{indent}_argc_validate_type '{target_name}' {name} "${variable}""#
+        ));
+    }
+    for (kind, range) in [("range", &data.range), ("length", &data.length)] {
+        if let Some(range) = range {
+            let min = range.min().map(|v| v.to_string()).unwrap_or_default();
+            let max = range.max().map(|v| v.to_string()).unwrap_or_default();
+            let inclusive = if range.inclusive() { "1" } else { "" };
+            output.push_str(&format!(
+                r#"
+{indent}_argc_validate_range '{target_name}' {kind} "{min}" "{max}" "{inclusive}" "${variable}""#
+            ));
+        }
+    }
+    if let Some(pattern) = &data.pattern {
+        let ere_pattern = escape_shell_words(&to_ere_pattern(pattern));
+        let render_pattern = escape_shell_words(pattern);
+        output.push_str(&format!(
+            r#"
+{indent}_argc_validate_pattern '{target_name}' {ere_pattern} {render_pattern} "${variable}""#
+        ));
+    }
+    output
+}
+
+/// Translate the perl-style classes that bash's `=~` (POSIX ERE) lacks.
+fn to_ere_pattern(pattern: &str) -> String {
+    let mut output = String::new();
+    let mut in_bracket = false;
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let Some(next) = chars.next() else {
+                    output.push(c);
+                    break;
+                };
+                let (class, negated) = match next {
+                    'd' | 'D' => ("0-9", next == 'D'),
+                    'w' | 'W' => ("A-Za-z0-9_", next == 'W'),
+                    's' | 'S' => ("[:space:]", next == 'S'),
+                    _ => {
+                        output.push(c);
+                        output.push(next);
+                        continue;
+                    }
+                };
+                match (in_bracket, negated) {
+                    (true, false) => output.push_str(class),
+                    (true, true) => {
+                        output.push(c);
+                        output.push(next);
+                    }
+                    (false, false) => output.push_str(&format!("[{class}]")),
+                    (false, true) => output.push_str(&format!("[^{class}]")),
+                }
+            }
+            '[' if !in_bracket => {
+                in_bracket = true;
+                output.push(c);
+                if let Some(v) = chars.next_if_eq(&'^') {
+                    output.push(v);
+                }
+                if let Some(v) = chars.next_if_eq(&']') {
+                    output.push(v);
+                }
+            }
+            ']' if in_bracket => {
+                in_bracket = false;
+                output.push(c);
+            }
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+fn build_deprecated(
+    messages: &Messages,
+    target: &str,
+    name: &str,
+    message: &str,
+    indent: usize,
+) -> String {
+    let indent = build_indent(indent);
+    let name = format_message(target, &[("name", name)]);
+    let warning = escape_shell_words(&render_deprecated(messages, &name, message));
+    format!(
+        r#"
+{indent}_argc_warnings+=({warning})"#
+    )
+}
+
 fn build_indent(indent_level: usize) -> String {
     "    ".repeat(indent_level)
 }
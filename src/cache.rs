@@ -0,0 +1,115 @@
+use crate::{
+    command::Command,
+    locale::format_message,
+    parser::{parse, Event, EventData},
+    runtime::{collect_files, expand_glob, Runtime},
+    utils::META_CACHE,
+    ArgcValue,
+};
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+
+/// Project-local directory where the results of cached recipes are recorded
+pub const CACHE_DIR: &str = ".argc-cache";
+
+/// Compute the cache key of the recipe that the evaluated `values` would run, in the project dir `cwd`.
+///
+/// Returns `(message, key)` if the recipe has `@meta cache`, the message telling that the recipe
+/// is cached is in the locale of the script. The key is a hash of the
+/// files declared by `@meta sources`, the recipe's function body and the evaluated argument values.
+pub fn cache_key<T: Runtime>(
+    runtime: T,
+    source: &str,
+    root_name: &str,
+    values: &[ArgcValue],
+    cwd: &str,
+) -> Result<Option<(String, String)>> {
+    let mut command_fn = None;
+    for value in values {
+        match value {
+            ArgcValue::CommandFn(name) => command_fn = Some(name),
+            ArgcValue::Error(_) | ArgcValue::UpToDate(_) => return Ok(None),
+            _ => {}
+        }
+    }
+    let Some(command_fn) = command_fn else {
+        return Ok(None);
+    };
+    let events = parse(source)?;
+    let root_cmd = Command::new_with_events(&events, root_name)?;
+    let Some(cmd) = find_cmd(&root_cmd, command_fn) else {
+        return Ok(None);
+    };
+    if !cmd.has_metadata(META_CACHE) {
+        return Ok(None);
+    }
+    let mut hasher = Sha256::new();
+    hash_field(&mut hasher, command_fn.as_bytes());
+    hash_field(&mut hasher, fn_body(source, &events, command_fn).as_bytes());
+    // Hash the evaluated values rather than the raw args, so defaults and env-bound values count
+    for value in values {
+        if matches!(value, ArgcValue::Multiple(id, _) if id == "_args") {
+            continue;
+        }
+        hash_field(&mut hasher, format!("{value:?}").as_bytes());
+    }
+    let mut files = vec![];
+    for pattern in cmd.meta_sources() {
+        for path in expand_glob(runtime, cwd, &pattern) {
+            collect_files(runtime, &path, &mut files);
+        }
+    }
+    files.sort();
+    files.dedup();
+    for path in files {
+        let name = path.strip_prefix(cwd).unwrap_or(&path);
+        hash_field(&mut hasher, name.as_bytes());
+        let Some(content) = runtime.read(&path) else {
+            return Ok(None);
+        };
+        hash_field(&mut hasher, &content);
+    }
+    let key = hasher
+        .finalize()
+        .iter()
+        .map(|v| format!("{v:02x}"))
+        .collect();
+    let messages = root_cmd.resolve_locale(runtime).messages();
+    let message = format_message(messages.cached, &[("name", &cmd.cmd_name())]);
+    Ok(Some((message, key)))
+}
+
+fn find_cmd<'a>(cmd: &'a Command, command_fn: &str) -> Option<&'a Command> {
+    if cmd.command_fn.as_deref() == Some(command_fn) {
+        return Some(cmd);
+    }
+    cmd.subcommands.iter().find_map(|v| find_cmd(v, command_fn))
+}
+
+/// Source lines from the function definition up to the next tag or function
+fn fn_body(source: &str, events: &[Event], name: &str) -> String {
+    let Some(index) = events
+        .iter()
+        .position(|v| matches!(&v.data, EventData::Func(v) if v == name))
+    else {
+        return String::new();
+    };
+    let start = events[index].position;
+    let end = events
+        .get(index + 1)
+        .map(|v| v.position)
+        .unwrap_or(usize::MAX);
+    let lines: Vec<&str> = source
+        .lines()
+        .enumerate()
+        .filter(|(i, _)| *i + 1 >= start && *i + 1 < end)
+        .map(|(_, line)| line)
+        .collect();
+    lines.join("\n")
+}
+
+fn hash_field(hasher: &mut Sha256, data: &[u8]) {
+    hasher.update((data.len() as u64).to_le_bytes());
+    hasher.update(data);
+}
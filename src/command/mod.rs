@@ -1,26 +1,37 @@
 mod names_checker;
+mod param_group;
 mod share_data;
 
 use self::names_checker::NamesChecker;
+pub(crate) use self::param_group::{ParamGroup, ParamGroupKind};
 use self::share_data::ShareData;
 
 use crate::argc_value::ArgcValue;
+use crate::locale::Locale;
+#[cfg(any(feature = "build", feature = "eval"))]
+use crate::locale::{format_message, Messages};
 #[cfg(feature = "eval")]
 use crate::matcher::Matcher;
 use crate::param::{EnvParam, FlagOptionParam, Param, PositionalParam};
 #[cfg(feature = "export")]
 use crate::param::{EnvValue, FlagOptionValue, PositionalValue};
+#[cfg(feature = "eval")]
+use crate::parsed_args::{ParseError, ParsedArgs};
 use crate::parser::{parse, parse_symbol, Event, EventData, EventScope, Position};
 use crate::runtime::Runtime;
 use crate::utils::{
-    AFTER_HOOK, BEFORE_HOOK, MAIN_NAME, META_AUTHOR, META_COMBINE_SHORTS, META_DEFAULT_SUBCOMMAND,
-    META_DOTENV, META_INHERIT_FLAG_OPTIONS, META_REQUIRE_TOOLS, META_SYMBOL, META_VERSION,
-    ROOT_NAME,
+    AFTER_HOOK, BEFORE_HOOK, MAIN_NAME, META_AUTHOR, META_CACHE, META_COMBINE_SHORTS,
+    META_DEFAULT_SUBCOMMAND, META_DEPS, META_DOTENV, META_INHERIT_FLAG_OPTIONS, META_LOCALE,
+    META_REQUIRE_TOOLS, META_SOURCES, META_SYMBOL, META_TARGETS, META_VERSION, ROOT_NAME,
 };
+#[cfg(any(feature = "build", feature = "eval"))]
+use crate::utils::{STYLE_HEADING, STYLE_LITERAL, STYLE_PLACEHOLDER, STYLE_RESET};
 use crate::Result;
 
 use anyhow::{anyhow, bail};
 use indexmap::{IndexMap, IndexSet};
+#[cfg(feature = "export")]
+use serde::Deserialize;
 use serde::Serialize;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -41,6 +52,20 @@ pub(crate) struct Command {
     pub(crate) subcommand_fns: HashMap<String, Position>,
     pub(crate) default_subcommand: Option<(usize, Position)>,
     pub(crate) aliases: Option<(Vec<String>, Position)>,
+    pub(crate) deprecated_aliases: IndexMap<String, String>,
+    // (dependency fns, position)
+    pub(crate) deps: Option<(Vec<String>, Position)>,
+    // Transitive dependencies in topological order, grouped into stages that only rely on earlier ones
+    pub(crate) dep_stages: Vec<Vec<String>>,
+    pub(crate) deprecated: Option<String>,
+    pub(crate) hidden: bool,
+    pub(crate) heading: Option<String>,
+    // Heading applied to the flags/options that follow
+    pub(crate) current_heading: Option<String>,
+    // Heading applied to the subcommands that follow
+    pub(crate) current_subcommand_heading: Option<String>,
+    // (command, describe)
+    pub(crate) examples: Vec<(String, String)>,
     pub(crate) author: Option<String>,
     pub(crate) version: Option<String>,
     pub(crate) names_checker: NamesChecker,
@@ -49,6 +74,7 @@ pub(crate) struct Command {
     pub(crate) metadata: Vec<(String, String, Position)>,
     pub(crate) symbols: IndexMap<char, SymbolParam>,
     pub(crate) require_tools: IndexSet<String>,
+    pub(crate) param_groups: Vec<ParamGroup>,
     pub(crate) help_flags: Vec<&'static str>,
     pub(crate) version_flags: Vec<&'static str>,
 }
@@ -56,13 +82,19 @@ pub(crate) struct Command {
 impl Command {
     pub(crate) fn new(source: &str, root_name: &str) -> Result<Self> {
         let events = parse(source)?;
-        let mut root = Command::new_from_events(&events)?;
+        Self::new_with_events(&events, root_name)
+    }
+
+    pub(crate) fn new_with_events(events: &[Event], root_name: &str) -> Result<Self> {
+        let mut root = Command::new_from_events(events)?;
         root.share.borrow_mut().name = Some(root_name.to_string());
+        root.check_force_flag()?;
         root.update_recursively(vec![], IndexSet::new());
         if root.has_metadata(META_INHERIT_FLAG_OPTIONS) {
             root.inherit_flag_options();
         }
         root.inherit_envs();
+        root.resolve_param_groups()?;
         Ok(root)
     }
 
@@ -99,6 +131,16 @@ impl Command {
         Ok(matcher.to_arg_values())
     }
 
+    #[cfg(feature = "eval")]
+    pub(crate) fn parse<T: Runtime>(
+        &self,
+        runtime: T,
+        args: &[String],
+    ) -> std::result::Result<ParsedArgs, ParseError> {
+        let matcher = Matcher::new(runtime, self, args, false);
+        matcher.to_parsed_args()
+    }
+
     #[cfg(feature = "export")]
     pub(crate) fn export(&self) -> CommandValue {
         let mut extra: IndexMap<String, serde_json::Value> = IndexMap::new();
@@ -106,6 +148,17 @@ impl Command {
         if !require_tools.is_empty() {
             extra.insert("require_tools".into(), require_tools.into());
         }
+        let sources = self.meta_sources();
+        if !sources.is_empty() {
+            extra.insert("sources".into(), sources.into());
+        }
+        let targets = self.meta_targets();
+        if !targets.is_empty() {
+            extra.insert("targets".into(), targets.into());
+        }
+        if self.has_metadata(META_CACHE) {
+            extra.insert("cache".into(), true.into());
+        }
         if self.is_root() {
             if self.get_metadata(META_COMBINE_SHORTS).is_some() {
                 extra.insert("combine_shorts".into(), true.into());
@@ -140,6 +193,44 @@ impl Command {
                     .collect::<IndexMap<String, Option<String>>>()),
             );
         }
+        if let Some(deprecated) = &self.deprecated {
+            extra.insert("deprecated".into(), deprecated.clone().into());
+        }
+        if self.hidden {
+            extra.insert("hidden".into(), true.into());
+        }
+        if let Some(heading) = &self.heading {
+            extra.insert("heading".into(), heading.clone().into());
+        }
+        if !self.examples.is_empty() {
+            extra.insert(
+                "examples".into(),
+                serde_json::json!(self
+                    .examples
+                    .iter()
+                    .map(|(command, describe)| serde_json::json!({
+                        "command": command,
+                        "describe": describe,
+                    }))
+                    .collect::<Vec<_>>()),
+            );
+        }
+        if !self.deprecated_aliases.is_empty() {
+            extra.insert(
+                "deprecated_aliases".into(),
+                serde_json::json!(self.deprecated_aliases),
+            );
+        }
+        if !self.param_groups.is_empty() {
+            extra.insert(
+                "groups".into(),
+                serde_json::json!(self
+                    .param_groups
+                    .iter()
+                    .map(|v| v.export())
+                    .collect::<Vec<_>>()),
+            );
+        }
         extra.insert("command_fn".into(), self.command_fn.clone().into());
         let flag_options = self.all_flag_options().iter().map(|v| v.export()).collect();
         CommandValue {
@@ -159,7 +250,7 @@ impl Command {
     pub(crate) fn new_from_events(events: &[Event]) -> Result<Self> {
         let mut root_cmd = Command::default();
         let share_data = root_cmd.share.clone();
-        for event in events {
+        for (i, event) in events.iter().enumerate() {
             let Event { data, position } = event.clone();
             match data {
                 EventData::Describe(value) => {
@@ -198,7 +289,37 @@ impl Command {
                                 cmd.author = Some(value.clone());
                             }
                         }
-                        _ => {}
+                        META_DEPS => {
+                            if share_data.borrow().scope != EventScope::CmdStart {
+                                bail!("@meta(line {}) deps must follow @cmd", position)
+                            }
+                            let deps: Vec<String> = value
+                                .split(',')
+                                .map(|v| v.trim().to_string())
+                                .filter(|v| !v.is_empty())
+                                .collect();
+                            if deps.is_empty() {
+                                bail!("@meta(line {}) invalid deps value", position)
+                            }
+                            cmd.deps = Some((deps, position));
+                        }
+                        META_LOCALE => {
+                            let locale = Locale::parse(&value).ok_or_else(|| {
+                                anyhow!(
+                                    "@meta(line {}) unsupported locale `{}`, expected one of: {}",
+                                    position,
+                                    value,
+                                    Locale::NAMES.join(", ")
+                                )
+                            })?;
+                            share_data.borrow_mut().locale = Some(locale);
+                        }
+                        _ => {
+                            if let Some(kind) = ParamGroupKind::from_meta(&key) {
+                                cmd.param_groups
+                                    .push(ParamGroup::new(kind, &value, position)?);
+                            }
+                        }
                     }
                     cmd.metadata.push((key, value, position));
                 }
@@ -211,20 +332,97 @@ impl Command {
                     }
                     share_data.borrow_mut().cmd_pos = position;
                     share_data.borrow_mut().scope = EventScope::CmdStart;
+                    let heading = root_cmd.current_subcommand_heading.clone();
                     let subcmd = root_cmd.create_cmd();
+                    subcmd.heading = heading;
                     if !value.is_empty() {
                         subcmd.describe.clone_from(&value);
                     }
                 }
                 EventData::Aliases(values) => {
                     let cmd = Self::get_cmd(&mut root_cmd, "@alias", position)?;
-                    cmd.aliases = Some((values.to_vec(), position));
+                    match cmd.aliases.as_mut() {
+                        Some((aliases, _)) => aliases.extend(values.iter().cloned()),
+                        None => cmd.aliases = Some((values.to_vec(), position)),
+                    }
+                }
+                EventData::Deprecated(value) => {
+                    let cmd = Self::get_cmd(&mut root_cmd, "@deprecated", position)?;
+                    match preceding_event(events, i) {
+                        Some(EventData::Cmd(_)) => {
+                            cmd.deprecated = Some(value);
+                        }
+                        Some(EventData::Aliases(values)) => {
+                            for name in values {
+                                cmd.deprecated_aliases.insert(name.clone(), value.clone());
+                            }
+                        }
+                        Some(EventData::FlagOption(_)) => {
+                            if let Some(param) = cmd.flag_option_params.last_mut() {
+                                param.data_mut().deprecated = Some(value);
+                            }
+                        }
+                        _ => bail!(
+                            "@deprecated(line {}) must follow @cmd, @alias, @flag or @option",
+                            position
+                        ),
+                    }
+                }
+                EventData::Hidden => {
+                    let cmd = Self::get_cmd(&mut root_cmd, "@hidden", position)?;
+                    let data = match preceding_event(events, i) {
+                        Some(EventData::Cmd(_) | EventData::Aliases(_)) => {
+                            cmd.hidden = true;
+                            None
+                        }
+                        Some(EventData::FlagOption(_)) => {
+                            cmd.flag_option_params.last_mut().map(|v| v.data_mut())
+                        }
+                        Some(EventData::Positional(_)) => {
+                            cmd.positional_params.last_mut().map(|v| v.data_mut())
+                        }
+                        Some(EventData::Env(_)) => cmd.env_params.last_mut().map(|v| v.data_mut()),
+                        _ => bail!(
+                            "@hidden(line {}) must follow @cmd, @alias, @flag, @option, @arg or @env",
+                            position
+                        ),
+                    };
+                    if let Some(data) = data {
+                        data.hidden = true;
+                    }
+                }
+                EventData::Heading(value) => {
+                    let heading = if value.is_empty() { None } else { Some(value) };
+                    // Between functions, or right before a `@cmd`, it groups subcommands
+                    let for_subcommands = match share_data.borrow().scope {
+                        EventScope::FnEnd => true,
+                        EventScope::Root => {
+                            matches!(following_param_event(events, i), Some(EventData::Cmd(_)))
+                        }
+                        EventScope::CmdStart => false,
+                    };
+                    if for_subcommands {
+                        root_cmd.current_subcommand_heading = heading;
+                    } else {
+                        let cmd = Self::get_cmd(&mut root_cmd, "@heading", position)?;
+                        cmd.current_heading = heading;
+                    }
                 }
-                EventData::FlagOption(param) => {
+                EventData::Example(value) => {
+                    let cmd = Self::get_cmd(&mut root_cmd, "@example", position)?;
+                    if value.is_empty() {
+                        bail!("@example(line {}) missing command", position)
+                    }
+                    let (command, describe) = value.split_once('\n').unwrap_or((&value, ""));
+                    cmd.examples
+                        .push((command.to_string(), describe.trim().to_string()));
+                }
+                EventData::FlagOption(mut param) => {
                     param.guard().map_err(|err| {
                         anyhow!("{}(line {}) is invalid, {err}", param.tag_name(), position)
                     })?;
                     let cmd = Self::get_cmd(&mut root_cmd, param.tag_name(), position)?;
+                    param.data_mut().heading.clone_from(&cmd.current_heading);
                     if param.is_option() {
                         share_data.borrow_mut().add_param_fn(
                             position,
@@ -344,6 +542,7 @@ impl Command {
             }
         }
         root_cmd.share.borrow().check_param_fn()?;
+        root_cmd.resolve_deps()?;
         Ok(root_cmd)
     }
 
@@ -359,14 +558,23 @@ impl Command {
     }
 
     pub(crate) fn meta_require_tools(&self) -> Vec<String> {
-        let raw_require_tools = self.get_metadata(META_REQUIRE_TOOLS).unwrap_or_default();
-        if raw_require_tools.is_empty() {
+        self.meta_list(META_REQUIRE_TOOLS)
+    }
+
+    pub(crate) fn meta_sources(&self) -> Vec<String> {
+        self.meta_list(META_SOURCES)
+    }
+
+    pub(crate) fn meta_targets(&self) -> Vec<String> {
+        self.meta_list(META_TARGETS)
+    }
+
+    fn meta_list(&self, key: &str) -> Vec<String> {
+        let raw_value = self.get_metadata(key).unwrap_or_default();
+        if raw_value.is_empty() {
             vec![]
         } else {
-            raw_require_tools
-                .split(',')
-                .map(|v| v.to_string())
-                .collect()
+            raw_value.split(',').map(|v| v.to_string()).collect()
         }
     }
 
@@ -433,9 +641,26 @@ impl Command {
         }
     }
 
+    /// Names of the visible subcommands, for the error messages and their suggestions
+    ///
+    /// Deprecated subcommands and aliases are left out, they shouldn't be suggested.
     pub(crate) fn list_subcommand_names(&self) -> Vec<String> {
         self.subcommands
             .iter()
+            .filter(|v| !v.hidden && v.deprecated.is_none())
+            .flat_map(|v| {
+                v.list_names()
+                    .into_iter()
+                    .filter(|name| !v.deprecated_aliases.contains_key(name))
+            })
+            .collect()
+    }
+
+    /// Names of the visible flags and options that aren't deprecated, suggested for unknown arguments
+    pub(crate) fn list_flag_option_names(&self) -> Vec<String> {
+        self.flag_option_params
+            .iter()
+            .filter(|v| !v.hidden() && v.deprecated().is_none())
             .flat_map(|v| v.list_names())
             .collect()
     }
@@ -487,6 +712,18 @@ impl Command {
             && self.positional_params[0].terminated()
     }
 
+    /// Locale set by `@meta locale`, English by default
+    pub(crate) fn locale(&self) -> Locale {
+        self.share.borrow().locale.unwrap_or_default()
+    }
+
+    /// Locale of `@meta locale`, or else of the environment
+    #[cfg(any(feature = "eval", feature = "compgen"))]
+    pub(crate) fn resolve_locale<T: Runtime>(&self, runtime: T) -> Locale {
+        let locale = self.share.borrow().locale;
+        locale.unwrap_or_else(|| Locale::from_env(runtime))
+    }
+
     pub(crate) fn dotenv(&self) -> Option<&str> {
         let dotenv = self.get_metadata(META_DOTENV)?;
         let dotenv = if dotenv.is_empty() { ".env" } else { dotenv };
@@ -568,6 +805,14 @@ impl Command {
             }
         }
         let long_prefix = if single { "-" } else { "--" };
+        if !self.meta_targets().is_empty() {
+            self.flag_option_params
+                .push(FlagOptionParam::create_force_flag(
+                    long_prefix,
+                    self.locale().messages().force,
+                ));
+            describe = true;
+        }
         self.derived_flag_option_params.extend(
             [
                 self.create_help_flag(describe, long_prefix),
@@ -625,6 +870,84 @@ impl Command {
         }
     }
 
+    /// `@meta targets` generates `--force`, so a command with targets can't declare its own
+    fn check_force_flag(&self) -> Result<()> {
+        if let Some((_, _, position)) = self.metadata.iter().find(|(k, _, _)| k == META_TARGETS) {
+            if let Some(param) = self.find_flag_option("force") {
+                bail!(
+                    "@meta(line {}) targets conflicts with `{}`",
+                    position,
+                    param.long_name()
+                )
+            }
+        }
+        for subcmd in self.subcommands.iter() {
+            subcmd.check_force_flag()?;
+        }
+        Ok(())
+    }
+
+    fn resolve_param_groups(&mut self) -> Result<()> {
+        for i in 0..self.param_groups.len() {
+            let group = &self.param_groups[i];
+            let mut ids = vec![];
+            for name in &group.names {
+                match self.find_flag_option(name) {
+                    Some(param) => ids.push(param.id().to_string()),
+                    None => bail!("@meta(line {}) unknown param `{}`", group.position, name),
+                }
+            }
+            self.param_groups[i].ids = ids;
+        }
+        for subcmd in self.subcommands.iter_mut() {
+            subcmd.resolve_param_groups()?;
+        }
+        Ok(())
+    }
+
+    fn resolve_deps(&mut self) -> Result<()> {
+        let mut graph = IndexMap::new();
+        collect_deps(self, &mut graph);
+        for (deps, position) in graph.values() {
+            for dep in deps {
+                if !graph.contains_key(dep) {
+                    bail!("@meta(line {}) unknown dependency `{}`", position, dep)
+                }
+            }
+        }
+        let mut depths = HashMap::new();
+        for name in graph.keys() {
+            dep_depth(name, &graph, &mut depths, &mut vec![])?;
+        }
+        self.update_dep_stages(&graph, &depths);
+        Ok(())
+    }
+
+    fn update_dep_stages(
+        &mut self,
+        graph: &IndexMap<String, (Vec<String>, Position)>,
+        depths: &HashMap<String, usize>,
+    ) {
+        if let Some((deps, _)) = &self.deps {
+            let mut visited = IndexSet::new();
+            for dep in deps {
+                visit_deps(dep, graph, &mut visited);
+            }
+            let mut stages: Vec<Vec<String>> = vec![];
+            for name in visited {
+                let depth = depths[&name];
+                if stages.len() <= depth {
+                    stages.resize(depth + 1, vec![]);
+                }
+                stages[depth].push(name);
+            }
+            self.dep_stages = stages;
+        }
+        for subcmd in self.subcommands.iter_mut() {
+            subcmd.update_dep_stages(graph, depths);
+        }
+    }
+
     fn add_positional_param(&mut self, param: PositionalParam, pos: Position) -> Result<()> {
         self.names_checker.check_positional(&param, pos)?;
         self.positional_params.push(param);
@@ -660,7 +983,11 @@ impl Command {
         if self.find_flag_option("help").is_some() {
             return None;
         }
-        let describe = if describe { "Print help" } else { "" };
+        let describe = if describe {
+            self.locale().messages().print_help
+        } else {
+            ""
+        };
         let short = if self.find_flag_option("-h").is_none() {
             Some("-h")
         } else {
@@ -680,7 +1007,11 @@ impl Command {
         if self.find_flag_option("version").is_some() {
             return None;
         }
-        let describe = if describe { "Print version" } else { "" };
+        let describe = if describe {
+            self.locale().messages().print_version
+        } else {
+            ""
+        };
         let short = if self.find_flag_option("-V").is_none() {
             Some("-V")
         } else {
@@ -696,7 +1027,13 @@ impl Command {
 
 #[cfg(any(feature = "build", feature = "eval"))]
 impl Command {
-    pub(crate) fn render_help(&self, wrap_width: Option<usize>) -> String {
+    pub(crate) fn render_help(
+        &self,
+        wrap_width: Option<usize>,
+        color: bool,
+        locale: Locale,
+    ) -> String {
+        let messages = locale.messages();
         let mut output = vec![];
         if self.version.is_some() {
             output.push(self.render_version());
@@ -710,40 +1047,61 @@ impl Command {
         if !output.is_empty() {
             output.push(String::new());
         }
-        output.push(self.render_usage());
+        output.push(self.render_usage(color, messages));
         output.push(String::new());
-        output.extend(self.render_positionals(wrap_width));
-        output.extend(self.render_flag_options(wrap_width));
-        output.extend(self.render_subcommands(wrap_width));
-        output.extend(self.render_envs(wrap_width));
+        output.extend(self.render_positionals(wrap_width, color, messages));
+        output.extend(self.render_flag_options(wrap_width, color, messages));
+        output.extend(self.render_subcommands(wrap_width, color, messages));
+        output.extend(self.render_envs(wrap_width, color, messages));
+        output.extend(self.render_examples(wrap_width, color, messages));
         if output.is_empty() {
             return "\n".to_string();
         }
         output.join("\n")
     }
 
-    fn render_usage(&self) -> String {
-        let mut output = vec!["USAGE:".to_string()];
-        output.extend(self.cmd_paths());
-        let required_options: Vec<String> = self
+    fn render_usage(&self, color: bool, messages: &Messages) -> String {
+        let mut output = self.cmd_paths();
+        let flag_options: Vec<&FlagOptionParam> = self
             .flag_option_params
+            .iter()
+            .filter(|v| !v.hidden())
+            .collect();
+        let required_options: Vec<String> = flag_options
             .iter()
             .filter(|v| v.required())
             .map(|v| v.render_name_notations())
             .collect();
-        if self.flag_option_params.len() != required_options.len() {
+        if flag_options.len() != required_options.len() {
             output.push("[OPTIONS]".to_string());
         }
         output.extend(required_options);
         if !self.subcommands.is_empty() {
             output.push("<COMMAND>".to_string());
         } else {
-            output.extend(self.positional_params.iter().map(|v| v.render_notation()));
+            output.extend(
+                self.positional_params
+                    .iter()
+                    .filter(|v| !v.hidden())
+                    .map(|v| v.render_notation()),
+            );
         }
-        output.join(" ")
+        let usage = output.join(" ");
+        let usage = if color {
+            paint_notations(&usage)
+        } else {
+            usage
+        };
+        let heading = format!("{}:", messages.usage);
+        format!("{} {usage}", paint(&heading, STYLE_HEADING, color))
     }
 
-    fn render_flag_options(&self, wrap_width: Option<usize>) -> Vec<String> {
+    fn render_flag_options(
+        &self,
+        wrap_width: Option<usize>,
+        color: bool,
+        messages: &Messages,
+    ) -> Vec<String> {
         let mut output = vec![];
         let default_subcmd = self.find_default_subcommand();
         if self.flag_option_params.is_empty()
@@ -754,38 +1112,82 @@ impl Command {
             return output;
         }
 
-        let params = match default_subcmd {
+        let params: Vec<&FlagOptionParam> = match default_subcmd {
             Some(subcmd) => [self.all_flag_options(), subcmd.all_flag_options()].concat(),
             None => self.all_flag_options(),
-        };
+        }
+        .into_iter()
+        .filter(|v| !v.hidden())
+        .collect();
+        if params.is_empty() {
+            return output;
+        }
+
+        let groups: Vec<&ParamGroup> = self
+            .param_groups
+            .iter()
+            .chain(default_subcmd.iter().flat_map(|v| v.param_groups.iter()))
+            .collect();
+
+        // The help and version flags are described in the locale of `@meta locale`, follow the help's one
+        let derived: Vec<&FlagOptionParam> = self
+            .derived_flag_option_params
+            .iter()
+            .chain(
+                default_subcmd
+                    .iter()
+                    .flat_map(|v| v.derived_flag_option_params.iter()),
+            )
+            .filter(|v| !v.describe().is_empty())
+            .collect();
 
         let mut value_size = 0;
-        let list: IndexMap<String, String> = params
-            .into_iter()
+        let list: IndexMap<String, (Option<&str>, String)> = params
+            .iter()
             .map(|param| {
                 let value = param.render_body();
-                let describe = param.render_describe();
+                let describe = match param.id() {
+                    "help" if derived.iter().any(|v| std::ptr::eq(*v, *param)) => {
+                        messages.print_help.to_string()
+                    }
+                    "version" if derived.iter().any(|v| std::ptr::eq(*v, *param)) => {
+                        messages.print_version.to_string()
+                    }
+                    _ if param.is_force_override() => messages.force.to_string(),
+                    _ => param.render_describe(messages),
+                };
+                let describe = render_param_groups(messages, describe, param, &groups, &params);
                 value_size = value_size.max(value.len());
-                (value, describe)
+                (value, (param.heading().map(|v| v.as_str()), describe))
             })
             .collect();
         value_size += 2;
-        output.push("OPTIONS:".to_string());
-        render_list(
-            &mut output,
-            list.into_iter().collect(),
-            value_size,
-            wrap_width,
-        );
+        let list = list
+            .into_iter()
+            .map(|(value, (heading, describe))| (heading, (value, describe)))
+            .collect();
+        for (heading, list) in group_by_heading(list) {
+            let heading = format!("{}:", heading.unwrap_or(messages.options));
+            output.push(paint(&heading, STYLE_HEADING, color));
+            render_list(&mut output, list, value_size, wrap_width, color);
+        }
         output
     }
 
-    fn render_positionals(&self, wrap_width: Option<usize>) -> Vec<String> {
+    fn render_positionals(
+        &self,
+        wrap_width: Option<usize>,
+        color: bool,
+        messages: &Messages,
+    ) -> Vec<String> {
         let mut output = vec![];
-        let params = match self.find_default_subcommand() {
+        let params: Vec<&PositionalParam> = match self.find_default_subcommand() {
             Some(subcmd) => &subcmd.positional_params,
             None => &self.positional_params,
-        };
+        }
+        .iter()
+        .filter(|v| !v.hidden())
+        .collect();
         if params.is_empty() {
             return output;
         }
@@ -795,21 +1197,30 @@ impl Command {
             .map(|param| {
                 let value = param.render_notation();
                 value_size = value_size.max(value.len());
-                (value, param.render_describe())
+                (value, param.render_describe(messages))
             })
             .collect();
         value_size += 2;
-        output.push("ARGS:".to_string());
-        render_list(&mut output, list, value_size, wrap_width);
+        let heading = format!("{}:", messages.args);
+        output.push(paint(&heading, STYLE_HEADING, color));
+        render_list(&mut output, list, value_size, wrap_width, color);
         output
     }
 
-    fn render_envs(&self, wrap_width: Option<usize>) -> Vec<String> {
+    fn render_envs(
+        &self,
+        wrap_width: Option<usize>,
+        color: bool,
+        messages: &Messages,
+    ) -> Vec<String> {
         let mut output = vec![];
-        let params = match self.find_default_subcommand() {
+        let params: Vec<&EnvParam> = match self.find_default_subcommand() {
             Some(subcmd) => &subcmd.env_params,
             None => &self.env_params,
-        };
+        }
+        .iter()
+        .filter(|v| !v.hidden())
+        .collect();
         if params.is_empty() {
             return output;
         }
@@ -819,56 +1230,113 @@ impl Command {
             .map(|param| {
                 let value = param.render_body();
                 value_size = value_size.max(value.len());
-                (value, param.render_describe())
+                (value, param.render_describe(messages))
             })
             .collect();
         value_size += 2;
-        output.push("ENVIRONMENTS:".to_string());
-        render_list(&mut output, list, value_size, wrap_width);
+        let heading = format!("{}:", messages.environments);
+        output.push(paint(&heading, STYLE_HEADING, color));
+        render_list(&mut output, list, value_size, wrap_width, color);
         output
     }
 
-    fn render_subcommands(&self, wrap_width: Option<usize>) -> Vec<String> {
+    fn render_subcommands(
+        &self,
+        wrap_width: Option<usize>,
+        color: bool,
+        messages: &Messages,
+    ) -> Vec<String> {
         let mut output = vec![];
-        if self.subcommands.is_empty() {
+        let subcommands: Vec<&Command> = self.subcommands.iter().filter(|v| !v.hidden).collect();
+        if subcommands.is_empty() {
             return output;
         }
         let mut value_size = 0;
-        let list: Vec<_> = self
-            .subcommands
+        let list: Vec<_> = subcommands
             .iter()
             .map(|subcmd| {
                 let value = subcmd.cmd_name();
                 value_size = value_size.max(value.len());
-                (value, subcmd.render_subcommand_describe())
+                (
+                    subcmd.heading.as_deref(),
+                    (value, subcmd.render_subcommand_describe(messages)),
+                )
             })
             .collect();
         value_size += 2;
-        output.push("COMMANDS:".to_string());
-        render_list(&mut output, list, value_size, wrap_width);
+        for (heading, list) in group_by_heading(list) {
+            let heading = format!("{}:", heading.unwrap_or(messages.commands));
+            output.push(paint(&heading, STYLE_HEADING, color));
+            render_list(&mut output, list, value_size, wrap_width, color);
+        }
+        output
+    }
+
+    fn render_examples(
+        &self,
+        wrap_width: Option<usize>,
+        color: bool,
+        messages: &Messages,
+    ) -> Vec<String> {
+        let mut output = vec![];
+        if self.examples.is_empty() {
+            return output;
+        }
+        let value_size = self
+            .examples
+            .iter()
+            .map(|(v, _)| v.len())
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let heading = format!("{}:", messages.examples);
+        output.push(paint(&heading, STYLE_HEADING, color));
+        render_list(
+            &mut output,
+            self.examples.clone(),
+            value_size,
+            wrap_width,
+            false,
+        );
         output
     }
 
-    fn render_subcommand_describe(&self) -> String {
+    fn render_subcommand_describe(&self, messages: &Messages) -> String {
         let mut output = self.describe_oneline().to_string();
         if let Some((aliases, _)) = &self.aliases {
+            let aliases: Vec<&str> = aliases
+                .iter()
+                .filter(|v| !self.deprecated_aliases.contains_key(*v))
+                .map(|v| v.as_str())
+                .collect();
+            if !aliases.is_empty() {
+                if !output.is_empty() {
+                    output.push(' ')
+                }
+                output.push_str(&format_message(
+                    messages.aliases,
+                    &[("aliases", &aliases.join(", "))],
+                ));
+            }
+        }
+        if self.has_metadata(META_DEFAULT_SUBCOMMAND) {
             if !output.is_empty() {
                 output.push(' ')
             }
-            output.push_str(&format!("[aliases: {}]", aliases.join(", ")));
+            output.push_str(messages.default_subcommand);
         }
-        if self.has_metadata(META_DEFAULT_SUBCOMMAND) {
+        if self.deprecated.is_some() {
             if !output.is_empty() {
                 output.push(' ')
             }
-            output.push_str("[default]");
+            output.push_str(messages.deprecated);
         }
         output
     }
 }
 
 #[cfg(feature = "export")]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CommandValue {
     pub name: String,
     pub describe: String,
@@ -885,6 +1353,148 @@ pub struct CommandValue {
 
 pub(crate) type SymbolParam = (String, Option<String>);
 
+#[cfg(any(feature = "build", feature = "eval"))]
+fn render_param_groups(
+    messages: &Messages,
+    describe: String,
+    param: &FlagOptionParam,
+    groups: &[&ParamGroup],
+    params: &[&FlagOptionParam],
+) -> String {
+    let render_names = |ids: &[String]| {
+        ids.iter()
+            .filter_map(|id| params.iter().find(|v| v.id() == id))
+            .map(|v| v.long_name())
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    let id = param.id();
+    let mut output = describe;
+    let sep = if output.contains('\n') { '\n' } else { ' ' };
+    for group in groups {
+        if !group.ids.iter().any(|v| v == id) {
+            continue;
+        }
+        let text = match group.kind {
+            ParamGroupKind::Conflicts => {
+                let others: Vec<String> = group.ids.iter().filter(|v| *v != id).cloned().collect();
+                format_message(
+                    messages.conflicts_with,
+                    &[("names", &render_names(&others))],
+                )
+            }
+            ParamGroupKind::Requires => {
+                if group.ids[0] != id {
+                    continue;
+                }
+                format_message(
+                    messages.requires,
+                    &[("names", &render_names(&group.ids[1..]))],
+                )
+            }
+            ParamGroupKind::OneOfRequired => format_message(
+                messages.one_of_required,
+                &[("names", &render_names(&group.ids))],
+            ),
+        };
+        if !output.is_empty() {
+            output.push(sep);
+        }
+        output.push_str(&text);
+    }
+    output
+}
+
+/// Find the event that `@deprecated`/`@hidden` at index `i` applies to, they can be stacked.
+fn preceding_event(events: &[Event], i: usize) -> Option<&EventData> {
+    events[..i]
+        .iter()
+        .rev()
+        .map(|v| &v.data)
+        .find(|v| !matches!(v, EventData::Deprecated(_) | EventData::Hidden))
+}
+
+/// Find the `@cmd`, `@flag`, `@option`, `@arg` or `@env` that follows the event at index `i`
+fn following_param_event(events: &[Event], i: usize) -> Option<&EventData> {
+    events[i + 1..].iter().map(|v| &v.data).find(|v| {
+        matches!(
+            v,
+            EventData::Cmd(_)
+                | EventData::FlagOption(_)
+                | EventData::Positional(_)
+                | EventData::Env(_)
+        )
+    })
+}
+
+fn collect_deps(cmd: &Command, graph: &mut IndexMap<String, (Vec<String>, Position)>) {
+    for subcmd in &cmd.subcommands {
+        if let Some(name) = &subcmd.match_fn {
+            let value = match &subcmd.deps {
+                Some((deps, position)) => (deps.clone(), *position),
+                None => (vec![], Position::default()),
+            };
+            graph.insert(name.clone(), value);
+        }
+        collect_deps(subcmd, graph);
+    }
+}
+
+/// Length of the longest dependency chain below `name`, failing on cycles
+fn dep_depth(
+    name: &str,
+    graph: &IndexMap<String, (Vec<String>, Position)>,
+    depths: &mut HashMap<String, usize>,
+    stack: &mut Vec<String>,
+) -> Result<usize> {
+    if let Some(depth) = depths.get(name) {
+        return Ok(*depth);
+    }
+    if let Some(index) = stack.iter().position(|v| v == name) {
+        let mut cycle = stack[index..].to_vec();
+        cycle.push(name.to_string());
+        let (_, position) = &graph[&stack[index]];
+        bail!(
+            "@meta(line {}) dependency cycle detected: {}",
+            position,
+            cycle.join(" -> ")
+        )
+    }
+    stack.push(name.to_string());
+    let mut depth = 0;
+    for dep in &graph[name].0 {
+        depth = depth.max(dep_depth(dep, graph, depths, stack)? + 1);
+    }
+    stack.pop();
+    depths.insert(name.to_string(), depth);
+    Ok(depth)
+}
+
+fn visit_deps(
+    name: &str,
+    graph: &IndexMap<String, (Vec<String>, Position)>,
+    visited: &mut IndexSet<String>,
+) {
+    if visited.contains(name) {
+        return;
+    }
+    for dep in &graph[name].0 {
+        visit_deps(dep, graph, visited);
+    }
+    visited.insert(name.to_string());
+}
+
+/// Group items by heading, items without a heading come first.
+pub(crate) fn group_by_heading<T>(list: Vec<(Option<&str>, T)>) -> IndexMap<Option<&str>, Vec<T>> {
+    let mut groups: IndexMap<Option<&str>, Vec<T>> = IndexMap::new();
+    groups.insert(None, vec![]);
+    for (heading, item) in list {
+        groups.entry(heading).or_default().push(item);
+    }
+    groups.retain(|_, v| !v.is_empty());
+    groups
+}
+
 fn retrieve_cmd<'a>(cmd: &'a mut Command, paths: &[String]) -> Option<&'a mut Command> {
     if paths.is_empty() {
         return Some(cmd);
@@ -923,26 +1533,35 @@ fn sanitize_cmd_name(name: &str) -> String {
     name.trim_end_matches('_').to_string()
 }
 
+#[cfg(any(feature = "build", feature = "eval"))]
 fn render_list(
     output: &mut Vec<String>,
     list: Vec<(String, String)>,
     value_size: usize,
     wrap_width: Option<usize>,
+    color: bool,
 ) {
     let mut mapped_list = vec![];
     let multiline = list.iter().any(|(_, describe)| describe.contains('\n'));
     for (value, describe) in list {
+        let styled_value = if color {
+            paint_notations(&value)
+        } else {
+            value.clone()
+        };
         let item = if describe.is_empty() {
             let maybe_newline = if multiline { "\n" } else { "" };
-            format!("  {value}{maybe_newline}")
+            format!("  {styled_value}{maybe_newline}")
         } else if multiline {
             format!(
-                "  {value}\n{}\n",
+                "  {styled_value}\n{}\n",
                 render_block(&" ".repeat(10), &describe, wrap_width)
             )
         } else {
+            // Align and wrap by the plain value, the styles take no room on the terminal
             let spaces = " ".repeat(value_size - value.len());
-            render_block(&format!("  {value}{spaces}"), &describe, wrap_width)
+            let block = render_block(&format!("  {value}{spaces}"), &describe, wrap_width);
+            format!("  {styled_value}{}", &block[2 + value.len()..])
         };
         mapped_list.push(item);
     }
@@ -954,6 +1573,63 @@ fn render_list(
     }
 }
 
+#[cfg(any(feature = "build", feature = "eval"))]
+fn paint(text: &str, style: &str, color: bool) -> String {
+    if color {
+        format!("{style}{text}{STYLE_RESET}")
+    } else {
+        text.to_string()
+    }
+}
+
+/// Style names such as `-f, --foo` as literals and notations such as `<FOO>...` as placeholders
+#[cfg(any(feature = "build", feature = "eval"))]
+fn paint_notations(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut output = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        match chars[i] {
+            ' ' | ',' | '=' => {
+                output.push(chars[i]);
+                i += 1;
+                continue;
+            }
+            open @ ('<' | '[') => {
+                let close = if open == '<' { '>' } else { ']' };
+                let mut depth = 0;
+                while i < chars.len() {
+                    if chars[i] == open {
+                        depth += 1;
+                    } else if chars[i] == close {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    i += 1;
+                }
+                i = (i + 1).min(chars.len());
+                if chars[i..].starts_with(&['.', '.', '.']) {
+                    i += 3;
+                }
+                let value: String = chars[start..i].iter().collect();
+                output.push_str(&paint(&value, STYLE_PLACEHOLDER, true));
+            }
+            _ => {
+                while i < chars.len() && !matches!(chars[i], ' ' | ',' | '=' | '<' | '[') {
+                    i += 1;
+                }
+                let value: String = chars[start..i].iter().collect();
+                output.push_str(&paint(&value, STYLE_LITERAL, true));
+            }
+        }
+    }
+    output
+}
+
+#[cfg(any(feature = "build", feature = "eval"))]
 fn render_block(name: &str, describe: &str, wrap_width: Option<usize>) -> String {
     let size = wrap_width.unwrap_or(999) - name.len();
     let empty = " ".repeat(name.len());
@@ -980,3 +1656,62 @@ fn render_block(name: &str, describe: &str, wrap_width: Option<usize>) -> String
         .collect::<Vec<String>>()
         .join("\n")
 }
+
+#[cfg(all(test, feature = "eval"))]
+mod tests {
+    use super::*;
+
+    fn strip_styles(text: &str) -> String {
+        [STYLE_HEADING, STYLE_LITERAL, STYLE_PLACEHOLDER, STYLE_RESET]
+            .iter()
+            .fold(text.to_string(), |text, style| text.replace(style, ""))
+    }
+
+    #[test]
+    fn test_paint_notations() {
+        let literal = |v: &str| format!("{STYLE_LITERAL}{v}{STYLE_RESET}");
+        let placeholder = |v: &str| format!("{STYLE_PLACEHOLDER}{v}{STYLE_RESET}");
+        assert_eq!(
+            paint_notations("-f, --foo <FOO>..."),
+            format!(
+                "{}, {} {}",
+                literal("-f"),
+                literal("--foo"),
+                placeholder("<FOO>...")
+            )
+        );
+        assert_eq!(
+            paint_notations("--bar[=<BAR>]"),
+            format!("{}{}", literal("--bar"), placeholder("[=<BAR>]"))
+        );
+        assert_eq!(
+            paint_notations("--baz=<BAZ>"),
+            format!("{}={}", literal("--baz"), placeholder("<BAZ>"))
+        );
+        assert_eq!(paint_notations("[FILES]..."), placeholder("[FILES]..."));
+    }
+
+    #[test]
+    fn test_render_help_color() {
+        let cmd = Command::new(
+            r#"
+# @flag -v --verbose        Print more
+# @option -f --format <FMT>  Output format
+# @arg files*               Files to read
+
+# @cmd Build it
+build() { :; }
+"#,
+            "prog",
+        )
+        .unwrap();
+        let plain = cmd.render_help(None, false, Locale::En);
+        let styled = cmd.render_help(None, true, Locale::En);
+        assert_ne!(styled, plain);
+        assert_eq!(strip_styles(&styled), plain);
+        assert!(styled.contains(&format!(
+            "{STYLE_LITERAL}--format{STYLE_RESET} {STYLE_PLACEHOLDER}<FMT>{STYLE_RESET}  Output format"
+        )));
+        assert!(styled.contains(&format!("{STYLE_HEADING}COMMANDS:{STYLE_RESET}")));
+    }
+}
@@ -0,0 +1,70 @@
+use crate::parser::Position;
+use crate::utils::{META_CONFLICTS, META_ONE_OF_REQUIRED, META_REQUIRES};
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum ParamGroupKind {
+    /// At most one of the params can be used.
+    Conflicts,
+    /// The first param requires all the others.
+    Requires,
+    /// At least one of the params must be used.
+    OneOfRequired,
+}
+
+impl ParamGroupKind {
+    pub(crate) fn from_meta(key: &str) -> Option<Self> {
+        match key {
+            META_CONFLICTS => Some(Self::Conflicts),
+            META_REQUIRES => Some(Self::Requires),
+            META_ONE_OF_REQUIRED => Some(Self::OneOfRequired),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ParamGroup {
+    pub(crate) kind: ParamGroupKind,
+    pub(crate) names: Vec<String>,
+    // Ids of the flags/options, filled once all params of the command are known
+    pub(crate) ids: Vec<String>,
+    pub(crate) position: Position,
+}
+
+impl ParamGroup {
+    pub(crate) fn new(kind: ParamGroupKind, value: &str, position: Position) -> Result<Self> {
+        let names: Vec<String> = value
+            .split(',')
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect();
+        if names.len() < 2 {
+            bail!("@meta(line {}) requires at least two params", position)
+        }
+        Ok(Self {
+            kind,
+            names,
+            ids: vec![],
+            position,
+        })
+    }
+
+    #[cfg(feature = "export")]
+    pub(crate) fn export(&self) -> ParamGroupValue {
+        ParamGroupValue {
+            kind: self.kind,
+            params: self.ids.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "export")]
+#[derive(Debug, Serialize)]
+pub(crate) struct ParamGroupValue {
+    pub(crate) kind: ParamGroupKind,
+    pub(crate) params: Vec<String>,
+}
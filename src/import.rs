@@ -0,0 +1,234 @@
+use crate::{
+    command::CommandValue,
+    param::{ChoiceValue, DefaultValue, EnvParam, FlagOptionParam, Param, PositionalParam},
+    utils::{AFTER_HOOK, BEFORE_HOOK, MAIN_NAME},
+};
+
+use anyhow::{Context, Result};
+use indexmap::IndexSet;
+use serde_json::Value;
+
+/// Generate an argc script skeleton from the json produced by `--argc-export`
+pub fn import(json: &str) -> Result<String> {
+    let root: CommandValue = serde_json::from_str(json).with_context(|| "Invalid export json")?;
+    let mut output = vec!["#!/usr/bin/env bash".to_string(), String::new()];
+    output.push("set -e".to_string());
+    output.push(String::new());
+
+    let mut subcommand_heading = None;
+    let mut lines = vec![];
+    if !root.describe.is_empty() {
+        lines.push(render_tag("@describe", &root.describe));
+    }
+    let metadata = root.extra.get("metadata").and_then(|v| v.as_object());
+    if let Some(version) = &root.version {
+        if !metadata.is_some_and(|v| v.contains_key("version")) {
+            lines.push(render_tag("@version", version));
+        }
+    }
+    if let Some(author) = &root.author {
+        if !metadata.is_some_and(|v| v.contains_key("author")) {
+            lines.push(render_tag("@author", author));
+        }
+    }
+    render_body(&mut lines, &root, &mut None);
+    if !lines.is_empty() {
+        output.extend(lines);
+        output.push(String::new());
+    }
+    if root.extra.get("command_fn").and_then(|v| v.as_str()) == Some(MAIN_NAME) {
+        output.push(render_fn(MAIN_NAME));
+        output.push(String::new());
+    }
+    for (key, name) in [("before_hook", BEFORE_HOOK), ("after_hook", AFTER_HOOK)] {
+        if root.extra.contains_key(key) {
+            output.push(format!("{name}() {{\n    :\n}}"));
+            output.push(String::new());
+        }
+    }
+    for subcmd in &root.subcommands {
+        render_subcommand(&mut output, subcmd, &[], &mut subcommand_heading);
+    }
+    let mut param_fns = IndexSet::new();
+    collect_param_fns(&root, &mut param_fns);
+    for name in param_fns {
+        output.push(render_fn(&name));
+        output.push(String::new());
+    }
+
+    output.push("# See more details at https://github.com/sigoden/argc".to_string());
+    output.push(r#"eval "$(argc --argc-eval "$0" "$@")""#.to_string());
+    output.push(String::new());
+    Ok(output.join("\n"))
+}
+
+fn render_subcommand(
+    output: &mut Vec<String>,
+    cmd: &CommandValue,
+    parents: &[String],
+    subcommand_heading: &mut Option<String>,
+) {
+    let mut paths = parents.to_vec();
+    paths.push(cmd.name.clone());
+    let heading = cmd.extra.get("heading").and_then(|v| v.as_str());
+    render_heading(output, heading, subcommand_heading);
+
+    output.push(render_tag("@cmd", &cmd.describe));
+    if let Some(deprecated) = cmd.extra.get("deprecated").and_then(|v| v.as_str()) {
+        output.push(render_tag("@deprecated", deprecated));
+    }
+    if cmd.extra.get("hidden").and_then(|v| v.as_bool()) == Some(true) {
+        output.push("# @hidden".to_string());
+    }
+    let deprecated_aliases = cmd
+        .extra
+        .get("deprecated_aliases")
+        .and_then(|v| v.as_object());
+    // Skip the alias derived from a command name that contains `_`
+    let aliases: Vec<&str> = cmd
+        .aliases
+        .iter()
+        .filter(|v| !cmd.name.contains('_') || **v != cmd.name.replace('_', "-"))
+        .filter(|v| !deprecated_aliases.is_some_and(|m| m.contains_key(*v)))
+        .map(|v| v.as_str())
+        .collect();
+    if !aliases.is_empty() {
+        output.push(format!("# @alias {}", aliases.join(",")));
+    }
+    for (alias, message) in deprecated_aliases.into_iter().flatten() {
+        output.push(format!("# @alias {alias}"));
+        output.push(render_tag(
+            "@deprecated",
+            message.as_str().unwrap_or_default(),
+        ));
+    }
+    render_body(output, cmd, &mut None);
+
+    let fn_name = if cmd.subcommands.is_empty() {
+        cmd.extra
+            .get("command_fn")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| paths.join("::"))
+    } else {
+        paths.join("::")
+    };
+    output.push(render_fn(&fn_name));
+    output.push(String::new());
+
+    let main_fn = format!("{}::{MAIN_NAME}", paths.join("::"));
+    if !cmd.subcommands.is_empty()
+        && cmd.extra.get("command_fn").and_then(|v| v.as_str()) == Some(main_fn.as_str())
+    {
+        output.push(render_fn(&main_fn));
+        output.push(String::new());
+    }
+    for subcmd in &cmd.subcommands {
+        render_subcommand(output, subcmd, &paths, subcommand_heading);
+    }
+}
+
+fn render_body(output: &mut Vec<String>, cmd: &CommandValue, heading: &mut Option<String>) {
+    if let Some(metadata) = cmd.extra.get("metadata").and_then(|v| v.as_object()) {
+        for (key, value) in metadata {
+            match value.as_str() {
+                Some(value) => output.push(format!("# @meta {key} {value}")),
+                None => output.push(format!("# @meta {key}")),
+            }
+        }
+    }
+    for value in cmd.flag_options.iter().filter(|v| !v.inherited) {
+        // Skip the help/version flags generated by argc
+        if value.flag && (value.id == "help" || value.id == "version") {
+            continue;
+        }
+        let param = FlagOptionParam::import(value);
+        render_heading(output, value.heading.as_deref(), heading);
+        output.push(render_tag(param.tag_name(), &param.render_source()));
+        if let Some(deprecated) = &value.deprecated {
+            output.push(render_tag("@deprecated", deprecated));
+        }
+        if value.hidden {
+            output.push("# @hidden".to_string());
+        }
+    }
+    for value in &cmd.positionals {
+        let param = PositionalParam::import(value);
+        output.push(render_tag(param.tag_name(), &param.render_source()));
+        if value.hidden {
+            output.push("# @hidden".to_string());
+        }
+    }
+    for value in cmd.envs.iter().filter(|v| !v.inherited) {
+        let param = EnvParam::import(value);
+        output.push(render_tag(param.tag_name(), &param.render_source()));
+        if value.hidden {
+            output.push("# @hidden".to_string());
+        }
+    }
+    if let Some(examples) = cmd.extra.get("examples").and_then(|v| v.as_array()) {
+        for example in examples {
+            let command = example["command"].as_str().unwrap_or_default();
+            let mut text = command.to_string();
+            if let Some(describe) = example.get("describe").and_then(Value::as_str) {
+                if !describe.is_empty() {
+                    text.push('\n');
+                    text.push_str(describe);
+                }
+            }
+            output.push(render_tag("@example", &text));
+        }
+    }
+}
+
+fn collect_param_fns(cmd: &CommandValue, output: &mut IndexSet<String>) {
+    let values = cmd
+        .flag_options
+        .iter()
+        .map(|v| (&v.default, &v.choice))
+        .chain(cmd.positionals.iter().map(|v| (&v.default, &v.choice)))
+        .chain(cmd.envs.iter().map(|v| (&v.default, &v.choice)));
+    for (default, choice) in values {
+        if let Some(DefaultValue::Fn(name)) = default {
+            output.insert(name.clone());
+        }
+        if let Some(ChoiceValue::Fn(name, _)) = choice {
+            output.insert(name.clone());
+        }
+    }
+    for subcmd in &cmd.subcommands {
+        collect_param_fns(subcmd, output);
+    }
+}
+
+fn render_heading(output: &mut Vec<String>, heading: Option<&str>, current: &mut Option<String>) {
+    if current.as_deref() != heading {
+        output.push(render_tag("@heading", heading.unwrap_or_default()));
+        *current = heading.map(|v| v.to_string());
+    }
+}
+
+fn render_tag(tag: &str, text: &str) -> String {
+    let mut output = format!("# {tag}");
+    for (i, line) in text.split('\n').enumerate() {
+        if i == 0 {
+            if !line.is_empty() {
+                output.push(' ');
+                output.push_str(line);
+            }
+        } else if line.is_empty() {
+            output.push_str("\n#");
+        } else {
+            output.push_str("\n# ");
+            if line.trim_start().starts_with('@') {
+                output.push('\\');
+            }
+            output.push_str(line);
+        }
+    }
+    output
+}
+
+fn render_fn(name: &str) -> String {
+    format!("{name}() {{\n    echo TODO {name}\n}}")
+}
@@ -1,41 +1,62 @@
 mod argc_value;
 #[cfg(feature = "build")]
 mod build;
+#[cfg(feature = "cache")]
+mod cache;
 mod command;
 #[cfg(feature = "compgen")]
 mod compgen;
 #[cfg(feature = "completions")]
 mod completions;
+#[cfg(feature = "export")]
+mod import;
+mod locale;
 #[cfg(feature = "mangen")]
 mod mangen;
+#[cfg(feature = "markdown")]
+mod markdown;
 #[cfg(any(feature = "eval", feature = "compgen"))]
 mod matcher;
 mod param;
+#[cfg(feature = "eval")]
+mod parsed_args;
 mod parser;
 mod runtime;
+#[cfg(feature = "export")]
+mod schema;
 #[cfg(any(feature = "compgen", feature = "completions"))]
 mod shell;
 pub mod utils;
 
 use anyhow::Result;
-pub use argc_value::ArgcValue;
+pub use argc_value::{ArgcValue, MatchError};
 #[cfg(feature = "build")]
 pub use build::build;
+#[cfg(feature = "cache")]
+pub use cache::{cache_key, CACHE_DIR};
 #[cfg(feature = "export")]
 pub use command::CommandValue;
 #[cfg(feature = "compgen")]
 pub use compgen::{compgen, compgen_kind, CompKind, COMPGEN_KIND_SYMBOL};
 #[cfg(feature = "completions")]
 pub use completions::generate_completions;
+#[cfg(feature = "export")]
+pub use import::import;
 #[cfg(feature = "mangen")]
 pub use mangen::mangen;
-pub use param::{ChoiceValue, DefaultValue};
+#[cfg(feature = "markdown")]
+pub use markdown::markdown;
+pub use param::{ChoiceValue, DefaultValue, RangeValue, ValueType};
 #[cfg(feature = "export")]
 pub use param::{EnvValue, FlagOptionValue, PositionalValue};
+#[cfg(feature = "eval")]
+pub use parsed_args::{ParseError, ParsedArgs};
 #[cfg(feature = "native-runtime")]
 pub use runtime::navite::NativeRuntime;
 #[cfg(any(feature = "eval", feature = "compgen"))]
 pub use runtime::Runtime;
+#[cfg(feature = "export")]
+pub use schema::{schema, EXPORT_SCHEMA};
 #[cfg(any(feature = "compgen", feature = "completions"))]
 pub use shell::Shell;
 
@@ -51,6 +72,49 @@ pub fn eval<T: Runtime>(
     cmd.eval(runtime, args, script_path, wrap_width)
 }
 
+/// Describe what the recipe selected by the args would run, in the locale of the script, along
+/// with the exit code the run would end with.
+#[cfg(feature = "eval-bash")]
+pub fn dry_run<T: Runtime>(
+    runtime: T,
+    script_content: &str,
+    args: &[String],
+    script_path: Option<&str>,
+    wrap_width: Option<usize>,
+) -> Result<(String, i32)> {
+    let Some(name) = args.first() else {
+        anyhow::bail!("No args, expected the command name")
+    };
+    let mut cmd = command::Command::new(script_content, name)?;
+    let messages = cmd.resolve_locale(runtime).messages();
+    let values = cmd.eval(runtime, args, script_path, wrap_width)?;
+    let exit = values
+        .iter()
+        .find_map(|v| match v {
+            ArgcValue::Error((_, exit, _)) => Some(*exit),
+            _ => None,
+        })
+        .unwrap_or_default();
+    Ok((ArgcValue::to_dry_run(&values, messages), exit))
+}
+
+/// Match the args against the script like `eval`, but return the values for use in Rust.
+///
+/// `args` starts with the command name. Errors of matching the args, including the requests for help or version, are `ParseError::Match`.
+#[cfg(feature = "eval")]
+pub fn parse<T: Runtime>(
+    runtime: T,
+    script_content: &str,
+    args: &[String],
+) -> std::result::Result<ParsedArgs, ParseError> {
+    let Some(name) = args.first() else {
+        return Err(ParseError::EmptyArgs);
+    };
+    let cmd = command::Command::new(script_content, name)
+        .map_err(|err| ParseError::InvalidScript(format!("{err:#}")))?;
+    cmd.parse(runtime, args)
+}
+
 #[cfg(feature = "export")]
 pub fn export(source: &str, root_name: &str) -> Result<CommandValue> {
     let cmd = command::Command::new(source, root_name)?;
@@ -0,0 +1,313 @@
+use crate::runtime::Runtime;
+
+/// Language of the built-in help and error messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Locale {
+    #[default]
+    En,
+    Zh,
+}
+
+impl Locale {
+    pub(crate) const NAMES: [&'static str; 2] = ["en", "zh"];
+
+    /// Parse a locale such as `zh`, `zh_CN` or `zh_CN.UTF-8`
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let lang = value
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match lang.as_str() {
+            "en" | "c" | "posix" => Some(Self::En),
+            "zh" => Some(Self::Zh),
+            _ => None,
+        }
+    }
+
+    /// Locale of the first set of `LC_ALL`, `LC_MESSAGES` and `LANG`, English if unsupported
+    pub(crate) fn from_env<T: Runtime>(runtime: T) -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| runtime.env_var(name))
+            .find(|v| !v.is_empty())
+            .and_then(|v| Self::parse(&v))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn messages(&self) -> &'static Messages {
+        match self {
+            Self::En => &EN,
+            Self::Zh => &ZH,
+        }
+    }
+}
+
+/// Catalog of the built-in messages, `{name}` placeholders are filled by `format_message`
+#[derive(Debug)]
+pub(crate) struct Messages {
+    // help
+    pub(crate) usage: &'static str,
+    pub(crate) args: &'static str,
+    pub(crate) options: &'static str,
+    pub(crate) commands: &'static str,
+    pub(crate) environments: &'static str,
+    pub(crate) examples: &'static str,
+    pub(crate) print_help: &'static str,
+    pub(crate) print_version: &'static str,
+    pub(crate) default_value: &'static str,
+    pub(crate) possible_values: &'static str,
+    pub(crate) value_type: &'static str,
+    pub(crate) range: &'static str,
+    pub(crate) length: &'static str,
+    pub(crate) pattern: &'static str,
+    pub(crate) env: &'static str,
+    pub(crate) aliases: &'static str,
+    pub(crate) default_subcommand: &'static str,
+    pub(crate) deprecated: &'static str,
+    pub(crate) conflicts_with: &'static str,
+    pub(crate) requires: &'static str,
+    pub(crate) one_of_required: &'static str,
+    pub(crate) force: &'static str,
+    // manpage
+    pub(crate) name: &'static str,
+    pub(crate) synopsis: &'static str,
+    pub(crate) description: &'static str,
+    pub(crate) subcommands: &'static str,
+    pub(crate) environment_variables: &'static str,
+    pub(crate) version: &'static str,
+    pub(crate) authors: &'static str,
+    // errors
+    pub(crate) env_target: &'static str,
+    pub(crate) bind_env_target: &'static str,
+    pub(crate) invalid_subcommand: &'static str,
+    pub(crate) missing_subcommand: &'static str,
+    pub(crate) unknown_argument: &'static str,
+    pub(crate) missing_required_arguments: &'static str,
+    pub(crate) missing_required_environments: &'static str,
+    pub(crate) not_multiple_argument: &'static str,
+    pub(crate) invalid_value: &'static str,
+    pub(crate) invalid_bind_environment: &'static str,
+    pub(crate) mismatch_values: &'static str,
+    pub(crate) no_flag_value: &'static str,
+    pub(crate) conflict_arguments: &'static str,
+    pub(crate) missing_dependent_arguments: &'static str,
+    pub(crate) missing_one_of_arguments: &'static str,
+    // hints below the errors
+    pub(crate) expected: &'static str,
+    pub(crate) subcommand_list: &'static str,
+    pub(crate) did_you_mean: &'static str,
+    // warnings and notices
+    pub(crate) deprecated_warning: &'static str,
+    pub(crate) deprecated_warning_with_message: &'static str,
+    pub(crate) command_target: &'static str,
+    pub(crate) alias_target: &'static str,
+    pub(crate) argument_target: &'static str,
+    pub(crate) up_to_date: &'static str,
+    pub(crate) cached: &'static str,
+    // dry run
+    pub(crate) dry_run_function: &'static str,
+    pub(crate) dry_run_dotenv: &'static str,
+    pub(crate) dry_run_require_tools: &'static str,
+    pub(crate) dry_run_dependencies: &'static str,
+    pub(crate) dry_run_skip_up_to_date: &'static str,
+    pub(crate) dry_run_hooks: &'static str,
+    pub(crate) dry_run_arguments: &'static str,
+    pub(crate) dry_run_variables: &'static str,
+    pub(crate) dry_run_environments: &'static str,
+    pub(crate) dry_run_warning: &'static str,
+    pub(crate) dry_run_up_to_date: &'static str,
+    pub(crate) dry_run_error: &'static str,
+}
+
+pub(crate) const EN: Messages = Messages {
+    usage: "USAGE",
+    args: "ARGS",
+    options: "OPTIONS",
+    commands: "COMMANDS",
+    environments: "ENVIRONMENTS",
+    examples: "EXAMPLES",
+    print_help: "Print help",
+    print_version: "Print version",
+    default_value: "[default: {value}]",
+    possible_values: "[possible values: {values}]",
+    value_type: "[type: {name}]",
+    range: "[range: {range}]",
+    length: "[length: {length}]",
+    pattern: "[pattern: /{pattern}/]",
+    env: "[env: {name}]",
+    aliases: "[aliases: {aliases}]",
+    default_subcommand: "[default]",
+    deprecated: "[deprecated]",
+    conflicts_with: "[conflicts with: {names}]",
+    requires: "[requires: {names}]",
+    one_of_required: "[one of required: {names}]",
+    force: "Run even if the targets are up to date",
+    name: "NAME",
+    synopsis: "SYNOPSIS",
+    description: "DESCRIPTION",
+    subcommands: "SUBCOMMANDS",
+    environment_variables: "ENVIRONMENT VARIABLES",
+    version: "VERSION",
+    authors: "AUTHORS",
+    env_target: "environment variable `{name}`",
+    bind_env_target: "environment variable `{env}` that bound to `{name}`",
+    invalid_subcommand: "error: `{cmd}` requires a subcommand but '{arg}' is not one of them",
+    missing_subcommand: "error: `{cmd}` requires a subcommand but one was not provided",
+    unknown_argument: "error: unexpected argument `{name}` found",
+    missing_required_arguments: "error: the following required arguments were not provided:",
+    missing_required_environments: "error: the following required environments were not provided:",
+    not_multiple_argument: "error: the argument `{name}` cannot be used multiple times",
+    invalid_value: "error: invalid value `{value}` for {target}",
+    invalid_bind_environment:
+        "error: environment variable `{env}` has invalid value for param '{name}'",
+    mismatch_values: "error: incorrect number of values for `{name}`",
+    no_flag_value: "error: flag `{name}` don't accept any value",
+    conflict_arguments: "error: the argument `{name}` cannot be used with `{other}`",
+    missing_dependent_arguments: "error: the argument `{name}` requires the following arguments:",
+    missing_one_of_arguments: "error: one of the following arguments must be provided:",
+    expected: "[expected: {expected}]",
+    subcommand_list: "[subcommands: {subcommands}]",
+    did_you_mean: "[did you mean: {value}]",
+    deprecated_warning: "warning: {name} is deprecated",
+    deprecated_warning_with_message: "warning: {name} is deprecated. {message}",
+    command_target: "command `{name}`",
+    alias_target: "alias `{name}`",
+    argument_target: "argument `{name}`",
+    up_to_date: "`{name}` is up to date",
+    cached: "`{name}` is cached",
+    dry_run_function: "Function",
+    dry_run_dotenv: "Dotenv",
+    dry_run_require_tools: "Require tools",
+    dry_run_dependencies: "Dependencies",
+    dry_run_skip_up_to_date: "Skip if up to date",
+    dry_run_hooks: "Hooks",
+    dry_run_arguments: "Arguments",
+    dry_run_variables: "Variables",
+    dry_run_environments: "Environments",
+    dry_run_warning: "Warning: {message}",
+    dry_run_up_to_date: "`{name}` is up to date, nothing would run",
+    dry_run_error: "Nothing would run, exit {exit} with output:",
+};
+
+const ZH: Messages = Messages {
+    usage: "用法",
+    args: "参数",
+    options: "选项",
+    commands: "命令",
+    environments: "环境变量",
+    examples: "示例",
+    print_help: "打印帮助信息",
+    print_version: "打印版本信息",
+    default_value: "[默认值：{value}]",
+    possible_values: "[可选值：{values}]",
+    value_type: "[类型：{name}]",
+    range: "[范围：{range}]",
+    length: "[长度：{length}]",
+    pattern: "[模式：/{pattern}/]",
+    env: "[环境变量：{name}]",
+    aliases: "[别名：{aliases}]",
+    default_subcommand: "[默认]",
+    deprecated: "[已弃用]",
+    conflicts_with: "[冲突：{names}]",
+    requires: "[需要：{names}]",
+    one_of_required: "[必须提供其一：{names}]",
+    force: "即使目标已是最新也运行",
+    name: "名称",
+    synopsis: "概要",
+    description: "描述",
+    subcommands: "子命令",
+    environment_variables: "环境变量",
+    version: "版本",
+    authors: "作者",
+    env_target: "环境变量 `{name}`",
+    bind_env_target: "绑定到 `{name}` 的环境变量 `{env}`",
+    invalid_subcommand: "错误：`{cmd}` 需要一个子命令，但 '{arg}' 不是其中之一",
+    missing_subcommand: "错误：`{cmd}` 需要一个子命令，但未提供",
+    unknown_argument: "错误：发现意外的参数 `{name}`",
+    missing_required_arguments: "错误：未提供以下必需的参数：",
+    missing_required_environments: "错误：未提供以下必需的环境变量：",
+    not_multiple_argument: "错误：参数 `{name}` 不能多次使用",
+    invalid_value: "错误：{target} 的值 `{value}` 无效",
+    invalid_bind_environment: "错误：环境变量 `{env}` 对参数 '{name}' 的值无效",
+    mismatch_values: "错误：`{name}` 的值数量不正确",
+    no_flag_value: "错误：标志 `{name}` 不接受任何值",
+    conflict_arguments: "错误：参数 `{name}` 不能与 `{other}` 一起使用",
+    missing_dependent_arguments: "错误：参数 `{name}` 需要以下参数：",
+    missing_one_of_arguments: "错误：必须提供以下参数之一：",
+    expected: "[预期：{expected}]",
+    subcommand_list: "[子命令：{subcommands}]",
+    did_you_mean: "[你是不是想用：{value}]",
+    deprecated_warning: "警告：{name} 已弃用",
+    deprecated_warning_with_message: "警告：{name} 已弃用。{message}",
+    command_target: "命令 `{name}`",
+    alias_target: "别名 `{name}`",
+    argument_target: "参数 `{name}`",
+    up_to_date: "`{name}` 已是最新",
+    cached: "`{name}` 已缓存",
+    dry_run_function: "函数",
+    dry_run_dotenv: "环境文件",
+    dry_run_require_tools: "所需工具",
+    dry_run_dependencies: "依赖",
+    dry_run_skip_up_to_date: "已是最新时跳过",
+    dry_run_hooks: "钩子",
+    dry_run_arguments: "参数",
+    dry_run_variables: "变量",
+    dry_run_environments: "环境变量",
+    dry_run_warning: "警告：{message}",
+    dry_run_up_to_date: "`{name}` 已是最新，不会运行任何内容",
+    dry_run_error: "不会运行任何内容，退出码 {exit}，输出：",
+};
+
+/// Fill the `{name}` placeholders of a message, unknown placeholders are kept as is
+pub(crate) fn format_message(template: &str, args: &[(&str, &str)]) -> String {
+    let mut output = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let arg = tail.find('}').and_then(|end| {
+            args.iter()
+                .find(|(name, _)| *name == &tail[1..end])
+                .map(|(_, value)| (end, *value))
+        });
+        match arg {
+            Some((end, value)) => {
+                output.push_str(value);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Locale::parse("zh_CN.UTF-8"), Some(Locale::Zh));
+        assert_eq!(Locale::parse("zh"), Some(Locale::Zh));
+        assert_eq!(Locale::parse("en_US.UTF-8"), Some(Locale::En));
+        assert_eq!(Locale::parse("C.UTF-8"), Some(Locale::En));
+        assert_eq!(Locale::parse("fr_FR"), None);
+    }
+
+    #[test]
+    fn test_format_message() {
+        assert_eq!(
+            format_message(
+                EN.conflict_arguments,
+                &[("name", "--a"), ("other", "{name}")]
+            ),
+            "error: the argument `--a` cannot be used with `{name}`"
+        );
+        assert_eq!(format_message("{unknown} {", &[]), "{unknown} {");
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{command::Command, param::Param, utils::META_MAN_SECTION};
+use crate::{
+    command::{group_by_heading, Command},
+    locale::{format_message, Messages},
+    param::{FlagOptionParam, Param},
+    utils::META_MAN_SECTION,
+};
 
 use anyhow::Result;
 use roff::{bold, italic, roman, Inline, Roff};
@@ -15,7 +20,7 @@ fn manpage_impl(output: &mut Vec<(String, String)>, cmd: &Command, section: &str
     let filename = format!("{}.{}", cmd.full_name(), section);
     let page = render_manpage(cmd, section);
     output.push((filename, page));
-    for subcmd in &cmd.subcommands {
+    for subcmd in cmd.subcommands.iter().filter(|v| !v.hidden) {
         manpage_impl(output, subcmd, section);
     }
 }
@@ -29,6 +34,7 @@ fn render_manpage(cmd: &Command, section: &str) -> String {
     render_options_section(&mut roff, cmd);
     render_subcommands_section(&mut roff, cmd, section);
     render_envs_section(&mut roff, cmd);
+    render_examples_section(&mut roff, cmd);
     render_version_section(&mut roff, cmd);
     render_author_section(&mut roff, cmd);
     roff.to_roff()
@@ -42,7 +48,7 @@ fn render_title(roff: &mut Roff, cmd: &Command, section: &str) {
 }
 
 fn render_name_section(roff: &mut Roff, cmd: &Command) {
-    roff.control("SH", ["NAME"]);
+    roff.control("SH", [cmd.locale().messages().name]);
     let name = cmd.full_name();
     let text = if !cmd.describe.is_empty() {
         format!("{} - {}", name, cmd.describe_oneline())
@@ -53,7 +59,7 @@ fn render_name_section(roff: &mut Roff, cmd: &Command) {
 }
 
 fn render_synopsis_section(roff: &mut Roff, cmd: &Command) {
-    roff.control("SH", ["SYNOPSIS"]);
+    roff.control("SH", [cmd.locale().messages().synopsis]);
     let name = cmd.cmd_paths().join(" ");
     let mut line = vec![bold(name)];
     if !cmd.flag_option_params.is_empty() {
@@ -62,7 +68,7 @@ fn render_synopsis_section(roff: &mut Roff, cmd: &Command) {
     if !cmd.subcommands.is_empty() {
         line.push(roman(" <COMMAND>"))
     } else {
-        for param in &cmd.positional_params {
+        for param in cmd.positional_params.iter().filter(|v| !v.hidden()) {
             line.push(roman(format!(" {}", param.render_notation())))
         }
     }
@@ -73,7 +79,7 @@ fn render_description_section(roff: &mut Roff, cmd: &Command) {
     if !cmd.describe.contains('\n') {
         return;
     }
-    roff.control("SH", ["DESCRIPTION"]);
+    roff.control("SH", [cmd.locale().messages().description]);
     let mut body = vec![];
     render_describe(&mut body, &cmd.describe);
     roff.text(body);
@@ -83,47 +89,20 @@ fn render_options_section(roff: &mut Roff, cmd: &Command) {
     if cmd.flag_option_params.is_empty() && cmd.positional_params.is_empty() {
         return;
     }
-    roff.control("SH", ["OPTIONS"]);
-    for param in cmd.all_flag_options() {
-        let mut header = vec![];
-        if let Some(short) = param.short() {
-            header.push(bold(short));
-            header.push(roman(", "));
-        }
-        header.push(bold(param.long_name()));
-        let notations = param.notations();
-        if notations.len() == 1 {
-            header.push(roman("="));
-            let notation = &notations[0];
-            let parts = match (param.required(), param.multiple_occurs()) {
-                (true, true) => vec![roman("<"), italic(notation), roman(">...")],
-                (false, true) => vec![roman("["), italic(notation), roman("]...")],
-                (true, false) => vec![roman("<"), italic(notation), roman(">")],
-                (false, false) => vec![italic(notation)],
-            };
-            header.extend(parts);
-        } else {
-            for notation in notations {
-                header.push(roman(" "));
-                header.push(italic(notation));
-            }
-        }
-        if let Some(value) = param.default_value() {
-            header.push(roman(format!(" [default: {value}]")));
-        }
-        let mut body = vec![];
-        let mut has_help_written = false;
-        if !param.describe().is_empty() {
-            has_help_written = true;
-            render_describe(&mut body, param.describe());
-        }
-        roff.control("TP", []);
-        roff.text(header);
-        roff.text(body);
-        render_choices(roff, param, has_help_written);
+    let flag_options: Vec<_> = cmd
+        .all_flag_options()
+        .into_iter()
+        .filter(|v| !v.hidden())
+        .map(|v| (v.heading().map(|v| v.as_str()), v))
+        .collect();
+    let mut groups = group_by_heading(flag_options);
+    let messages = cmd.locale().messages();
+    roff.control("SH", [messages.options]);
+    for param in groups.shift_remove(&None).unwrap_or_default() {
+        render_flag_option(roff, param, messages);
     }
 
-    for param in &cmd.positional_params {
+    for param in cmd.positional_params.iter().filter(|v| !v.hidden()) {
         let notation = param.notation();
         let mut header = match (param.required(), param.multiple_values()) {
             (true, true) => vec![roman("<"), italic(notation), roman(">...")],
@@ -132,8 +111,9 @@ fn render_options_section(roff: &mut Roff, cmd: &Command) {
             (false, false) => vec![roman("["), italic(notation), roman("]")],
         };
         if let Some(value) = param.default_value() {
-            header.push(roman(format!(" [default: {value}]")));
+            header.push(render_default_value(value, messages));
         }
+        render_constraints(&mut header, param, messages);
         let mut body = vec![];
         let mut has_help_written = false;
         if !param.describe().is_empty() {
@@ -145,37 +125,95 @@ fn render_options_section(roff: &mut Roff, cmd: &Command) {
         roff.text(body);
         render_choices(roff, param, has_help_written);
     }
+
+    for (heading, params) in groups {
+        let heading = heading.unwrap_or_default().to_uppercase();
+        roff.control("SH", [heading.as_str()]);
+        for param in params {
+            render_flag_option(roff, param, messages);
+        }
+    }
 }
 
-fn render_subcommands_section(roff: &mut Roff, cmd: &Command, section: &str) {
-    if cmd.subcommands.is_empty() {
-        return;
+fn render_flag_option(roff: &mut Roff, param: &FlagOptionParam, messages: &Messages) {
+    let mut header = vec![];
+    if let Some(short) = param.short() {
+        header.push(bold(short));
+        header.push(roman(", "));
     }
-    roff.control("SH", ["SUBCOMMANDS"]);
-    for subcmd in &cmd.subcommands {
-        roff.control("TP", []);
-        let name = subcmd.full_name();
-        roff.text([roman(format!("{}({})", name, section))]);
-        for line in subcmd.describe.lines() {
-            roff.text([roman(line)]);
+    header.push(bold(param.long_name()));
+    let notations = param.notations();
+    if notations.len() == 1 {
+        header.push(roman("="));
+        let notation = &notations[0];
+        let parts = match (param.required(), param.multiple_occurs()) {
+            (true, true) => vec![roman("<"), italic(notation), roman(">...")],
+            (false, true) => vec![roman("["), italic(notation), roman("]...")],
+            (true, false) => vec![roman("<"), italic(notation), roman(">")],
+            (false, false) => vec![italic(notation)],
+        };
+        header.extend(parts);
+    } else {
+        for notation in notations {
+            header.push(roman(" "));
+            header.push(italic(notation));
+        }
+    }
+    if let Some(value) = param.default_value() {
+        header.push(render_default_value(value, messages));
+    }
+    render_constraints(&mut header, param, messages);
+    let mut body = vec![];
+    let mut has_help_written = false;
+    if !param.describe().is_empty() {
+        has_help_written = true;
+        render_describe(&mut body, param.describe());
+    }
+    roff.control("TP", []);
+    roff.text(header);
+    roff.text(body);
+    render_choices(roff, param, has_help_written);
+}
+
+fn render_subcommands_section(roff: &mut Roff, cmd: &Command, section: &str) {
+    let subcommands: Vec<_> = cmd
+        .subcommands
+        .iter()
+        .filter(|v| !v.hidden)
+        .map(|v| (v.heading.as_deref(), v))
+        .collect();
+    for (heading, subcommands) in group_by_heading(subcommands) {
+        let heading = heading
+            .unwrap_or(cmd.locale().messages().subcommands)
+            .to_uppercase();
+        roff.control("SH", [heading.as_str()]);
+        for subcmd in subcommands {
+            roff.control("TP", []);
+            let name = subcmd.full_name();
+            roff.text([roman(format!("{}({})", name, section))]);
+            for line in subcmd.describe.lines() {
+                roff.text([roman(line)]);
+            }
         }
     }
 }
 
 fn render_envs_section(roff: &mut Roff, cmd: &Command) {
-    if cmd.env_params.is_empty() {
+    if cmd.env_params.iter().all(|v| v.hidden()) {
         return;
     }
-    roff.control("SH", ["ENVIRONMENT VARIABLES:"]);
-    for param in &cmd.env_params {
+    let messages = cmd.locale().messages();
+    roff.control("SH", [messages.environment_variables]);
+    for param in cmd.env_params.iter().filter(|v| !v.hidden()) {
         let mut header = vec![];
         header.push(italic(param.var_name()));
         if param.required() {
             header.push(roman("*"));
         }
         if let Some(value) = param.default_value() {
-            header.push(roman(format!(" [default: {value}]")));
+            header.push(render_default_value(value, messages));
         }
+        render_constraints(&mut header, param, messages);
         let mut body = vec![];
         let mut has_help_written = false;
         if !param.describe().is_empty() {
@@ -189,16 +227,32 @@ fn render_envs_section(roff: &mut Roff, cmd: &Command) {
     }
 }
 
+fn render_examples_section(roff: &mut Roff, cmd: &Command) {
+    if cmd.examples.is_empty() {
+        return;
+    }
+    roff.control("SH", [cmd.locale().messages().examples]);
+    for (command, describe) in &cmd.examples {
+        roff.control("TP", []);
+        roff.text([bold(command)]);
+        let mut body = vec![];
+        if !describe.is_empty() {
+            render_describe(&mut body, describe);
+        }
+        roff.text(body);
+    }
+}
+
 fn render_version_section(roff: &mut Roff, cmd: &Command) {
     if let Some(version) = &cmd.version {
-        roff.control("SH", ["VERSION"]);
+        roff.control("SH", [cmd.locale().messages().version]);
         roff.text([roman(version)]);
     }
 }
 
 fn render_author_section(roff: &mut Roff, cmd: &Command) {
     if let Some(author) = &cmd.author {
-        roff.control("SH", ["AUTHORS"]);
+        roff.control("SH", [cmd.locale().messages().authors]);
         roff.text([roman(author)]);
     }
 }
@@ -210,6 +264,31 @@ fn render_describe(body: &mut Vec<Inline>, describe: &str) {
     }
 }
 
+fn render_default_value(value: &str, messages: &Messages) -> Inline {
+    roman(format!(
+        " {}",
+        format_message(messages.default_value, &[("value", value)])
+    ))
+}
+
+fn render_constraints<T: Param>(header: &mut Vec<Inline>, param: &T, messages: &Messages) {
+    let mut push = |template: &str, args: &[(&str, &str)]| {
+        header.push(roman(format!(" {}", format_message(template, args))));
+    };
+    if let Some(value_type) = param.value_type() {
+        push(messages.value_type, &[("name", value_type.name())]);
+    }
+    if let Some(range) = param.range() {
+        push(messages.range, &[("range", &range.to_string())]);
+    }
+    if let Some(length) = param.length() {
+        push(messages.length, &[("length", &length.to_string())]);
+    }
+    if let Some(pattern) = param.pattern() {
+        push(messages.pattern, &[("pattern", pattern)]);
+    }
+}
+
 fn render_choices<T: Param>(roff: &mut Roff, param: &T, has_help_written: bool) {
     if let Some(values) = param.choice_values() {
         if has_help_written {
@@ -0,0 +1,162 @@
+use crate::{
+    command::{group_by_heading, Command},
+    locale::EN,
+    param::{FlagOptionParam, Param},
+};
+
+use anyhow::Result;
+
+pub fn markdown(source: &str, root_name: &str) -> Result<Vec<(String, String)>> {
+    let mut output = vec![];
+    let root_cmd = Command::new(source, root_name)?;
+    markdown_impl(&mut output, &root_cmd);
+    Ok(output)
+}
+
+fn markdown_impl(output: &mut Vec<(String, String)>, cmd: &Command) {
+    let filename = format!("{}.md", cmd.full_name());
+    let page = render_page(cmd);
+    output.push((filename, page));
+    for subcmd in cmd.subcommands.iter().filter(|v| !v.hidden) {
+        markdown_impl(output, subcmd);
+    }
+}
+
+fn render_page(cmd: &Command) -> String {
+    let mut output = vec![format!("# {}", cmd.full_name())];
+    if !cmd.describe.is_empty() {
+        output.push(cmd.describe.clone());
+    }
+    render_usage_section(&mut output, cmd);
+    render_arguments_section(&mut output, cmd);
+    render_options_section(&mut output, cmd);
+    render_subcommands_section(&mut output, cmd);
+    render_envs_section(&mut output, cmd);
+    render_examples_section(&mut output, cmd);
+    if let Some(version) = &cmd.version {
+        output.push(format!("## Version\n\n{version}"));
+    }
+    if let Some(author) = &cmd.author {
+        output.push(format!("## Authors\n\n{author}"));
+    }
+    let mut page = output.join("\n\n");
+    page.push('\n');
+    page
+}
+
+fn render_usage_section(output: &mut Vec<String>, cmd: &Command) {
+    let mut line = vec![cmd.cmd_paths().join(" ")];
+    if cmd.flag_option_params.iter().any(|v| !v.hidden()) {
+        line.push("[OPTIONS]".to_string());
+    }
+    if !cmd.subcommands.is_empty() {
+        line.push("<COMMAND>".to_string());
+    } else {
+        line.extend(
+            cmd.positional_params
+                .iter()
+                .filter(|v| !v.hidden())
+                .map(|v| v.render_notation()),
+        );
+    }
+    output.push(format!("## Usage\n\n```\n{}\n```", line.join(" ")));
+}
+
+fn render_arguments_section(output: &mut Vec<String>, cmd: &Command) {
+    let list: Vec<String> = cmd
+        .positional_params
+        .iter()
+        .filter(|v| !v.hidden())
+        .map(|param| render_item(&param.render_notation(), &param.render_describe(&EN)))
+        .collect();
+    if list.is_empty() {
+        return;
+    }
+    output.push(format!("## Arguments\n\n{}", list.join("\n")));
+}
+
+fn render_options_section(output: &mut Vec<String>, cmd: &Command) {
+    let flag_options: Vec<_> = cmd
+        .all_flag_options()
+        .into_iter()
+        .filter(|v| !v.hidden())
+        .map(|v| (v.heading().map(|v| v.as_str()), v))
+        .collect();
+    for (heading, params) in group_by_heading(flag_options) {
+        let list: Vec<String> = params.into_iter().map(render_flag_option).collect();
+        let title = match heading {
+            Some(heading) => format!("## {heading}"),
+            None => "## Options".to_string(),
+        };
+        output.push(format!("{title}\n\n{}", list.join("\n")));
+    }
+}
+
+fn render_flag_option(param: &FlagOptionParam) -> String {
+    render_item(param.render_body().trim(), &param.render_describe(&EN))
+}
+
+fn render_subcommands_section(output: &mut Vec<String>, cmd: &Command) {
+    let subcommands: Vec<_> = cmd
+        .subcommands
+        .iter()
+        .filter(|v| !v.hidden)
+        .map(|v| (v.heading.as_deref(), v))
+        .collect();
+    for (heading, subcommands) in group_by_heading(subcommands) {
+        let list: Vec<String> = subcommands
+            .into_iter()
+            .map(|subcmd| {
+                let mut item = format!("- [`{}`]({}.md)", subcmd.cmd_name(), subcmd.full_name());
+                let describe = subcmd.describe_oneline();
+                if !describe.is_empty() {
+                    item.push_str(&format!(": {describe}"));
+                }
+                let aliases = subcmd.list_alias_names();
+                if !aliases.is_empty() {
+                    item.push_str(&format!(" [aliases: {}]", aliases.join(", ")));
+                }
+                item
+            })
+            .collect();
+        let title = match heading {
+            Some(heading) => format!("## {heading}"),
+            None => "## Commands".to_string(),
+        };
+        output.push(format!("{title}\n\n{}", list.join("\n")));
+    }
+}
+
+fn render_envs_section(output: &mut Vec<String>, cmd: &Command) {
+    let list: Vec<String> = cmd
+        .env_params
+        .iter()
+        .filter(|v| !v.hidden())
+        .map(|param| render_item(param.render_body().trim(), &param.render_describe(&EN)))
+        .collect();
+    if list.is_empty() {
+        return;
+    }
+    output.push(format!("## Environment Variables\n\n{}", list.join("\n")));
+}
+
+fn render_examples_section(output: &mut Vec<String>, cmd: &Command) {
+    if cmd.examples.is_empty() {
+        return;
+    }
+    let list: Vec<String> = cmd
+        .examples
+        .iter()
+        .map(|(command, describe)| render_item(command, describe))
+        .collect();
+    output.push(format!("## Examples\n\n{}", list.join("\n")));
+}
+
+fn render_item(name: &str, describe: &str) -> String {
+    let mut item = format!("- `{name}`");
+    if !describe.is_empty() {
+        item.push_str(": ");
+        item.push_str(&describe.replace('\n', "\n  "));
+    }
+    item
+}
@@ -3,11 +3,12 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::{
-    argc_value::ArgcValue,
-    command::{Command, SymbolParam},
-    param::{ChoiceValue, FlagOptionParam, Param, ParamData, PositionalParam},
+    argc_value::{ArgcValue, MatchError},
+    command::{Command, ParamGroupKind, SymbolParam},
+    locale::{format_message, Locale},
+    param::{ChoiceValue, FlagOptionParam, Param, ParamData, PositionalParam, ValueType},
     runtime::Runtime,
-    utils::{argc_var_name, is_true_value, META_COMBINE_SHORTS},
+    utils::{argc_var_name, is_true_value, render_deprecated, META_COMBINE_SHORTS},
 };
 
 #[cfg(feature = "compgen")]
@@ -15,6 +16,12 @@ use crate::{
     compgen::{CompColor, CompItem},
     Shell,
 };
+#[cfg(feature = "eval")]
+use crate::{
+    parsed_args::{ParseError, ParsedArgs},
+    runtime::{collect_files, expand_glob},
+    utils::{did_you_mean, META_PARALLEL_DEPS},
+};
 
 use either::Either;
 use indexmap::{IndexMap, IndexSet};
@@ -33,6 +40,7 @@ pub(crate) struct Matcher<'a, 'b, T> {
     script_path: Option<String>,
     envs: HashMap<String, String>,
     wrap_width: Option<usize>,
+    locale: Locale,
     split_last_arg_at: Option<usize>,
     comp_option: Option<&'a str>,
 }
@@ -56,23 +64,6 @@ impl ArgComp {
     }
 }
 
-#[derive(Debug)]
-pub(crate) enum MatchError {
-    DisplayHelp,
-    DisplaySubcommandHelp(String),
-    DisplayVersion,
-    InvalidSubcommand(Option<String>),
-    UnknownArgument(usize, String),
-    MissingRequiredArguments(usize, Vec<String>),
-    MissingRequiredEnvironments(Vec<String>),
-    NotMultipleArgument(usize, String),
-    InvalidValue(usize, String, String, Vec<String>),
-    InvalidBindEnvironment(usize, String, String, String, Vec<String>),
-    InvalidEnvironment(usize, String, String, Vec<String>),
-    MismatchValues(usize, String),
-    NoFlagValue(usize, String),
-}
-
 impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
     pub(crate) fn new(
         runtime: T,
@@ -364,6 +355,8 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
             add_param_choice_fn(&mut choice_fns, param)
         }
 
+        let locale = root_cmd.resolve_locale(runtime);
+
         Self {
             runtime,
             cmds,
@@ -377,6 +370,7 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
             choice_fns,
             script_path: None,
             wrap_width: None,
+            locale,
             split_last_arg_at,
             comp_option,
             envs,
@@ -395,10 +389,18 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
     pub(crate) fn to_arg_values(&self) -> Vec<ArgcValue> {
         let bind_envs = self.build_bind_envs();
         if let Some(err) = self.validate(&bind_envs) {
-            return vec![ArgcValue::Error(self.stringify_match_error(&err))];
+            let (message, exit) = self.stringify_match_error(&err);
+            return vec![ArgcValue::Error((message, exit, err))];
         }
         let last_cmd = self.last_cmd();
-        let mut output = self.to_arg_values_base(&bind_envs);
+        // With dependencies, the targets are checked once they have run
+        if last_cmd.dep_stages.is_empty() && self.is_up_to_date() {
+            let name = last_cmd.cmd_name();
+            let message = self.render_up_to_date(&name);
+            return vec![ArgcValue::UpToDate((name, message))];
+        }
+        let mut output = self.deprecated_warnings();
+        output.extend(self.to_arg_values_base(&bind_envs));
         if last_cmd.positional_params.is_empty() && !self.positional_args.is_empty() {
             output.push(ArgcValue::ExtraPositionalMultiple(
                 self.positional_args.iter().map(|v| v.to_string()).collect(),
@@ -410,11 +412,141 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
             ));
         }
         if let Some(command_fn) = &last_cmd.command_fn {
+            if !last_cmd.dep_stages.is_empty() {
+                output.push(ArgcValue::Deps((
+                    last_cmd.dep_stages.clone(),
+                    last_cmd.has_metadata(META_PARALLEL_DEPS),
+                )));
+                if self.check_up_to_date() {
+                    output.push(ArgcValue::UpToDateCheck((
+                        self.render_up_to_date(&last_cmd.cmd_name()),
+                        last_cmd.meta_targets(),
+                        last_cmd.meta_sources(),
+                    )));
+                }
+            }
             output.push(ArgcValue::CommandFn(command_fn.clone()));
         }
         output
     }
 
+    #[cfg(feature = "eval")]
+    pub(crate) fn to_parsed_args(&self) -> Result<ParsedArgs, ParseError> {
+        let bind_envs = self.build_bind_envs();
+        if let Some(kind) = self.validate(&bind_envs) {
+            let (message, exit) = self.stringify_match_error(&kind);
+            return Err(ParseError::Match {
+                kind: Box::new(kind),
+                exit,
+                message,
+            });
+        }
+        let last_cmd = self.last_cmd();
+        let mut parsed_args = ParsedArgs::new(last_cmd.paths.clone(), last_cmd.command_fn.clone());
+        for value in self.to_arg_values_base(&bind_envs) {
+            parsed_args.add_value(value);
+        }
+        if last_cmd.positional_params.is_empty() && !self.positional_args.is_empty() {
+            parsed_args.add_value(ArgcValue::ExtraPositionalMultiple(
+                self.positional_args.iter().map(|v| v.to_string()).collect(),
+            ));
+        }
+        for param in &last_cmd.env_params {
+            if let Some(value) = self.envs.get(param.id()) {
+                parsed_args.add_env(param.id(), value);
+            }
+        }
+        Ok(parsed_args)
+    }
+
+    /// Whether the command has targets and `--force` was not passed
+    #[cfg(feature = "eval")]
+    fn check_up_to_date(&self) -> bool {
+        let level = self.cmds.len() - 1;
+        let last_cmd = self.last_cmd();
+        !last_cmd.meta_targets().is_empty()
+            && !self.flag_option_args[level].iter().any(|(_, _, id)| {
+                id.and_then(|id| last_cmd.flag_option_params.iter().find(|v| v.id() == id))
+                    .is_some_and(|param| param.is_force_override())
+            })
+    }
+
+    #[cfg(feature = "eval")]
+    fn is_up_to_date(&self) -> bool {
+        if !self.check_up_to_date() {
+            return false;
+        }
+        let last_cmd = self.last_cmd();
+        let targets = last_cmd.meta_targets();
+        let Some(cwd) = self.runtime.current_dir() else {
+            return false;
+        };
+        let mut oldest_target = None;
+        for target in &targets {
+            let path = self.runtime.join_path(&cwd, &[target]);
+            match self.runtime.modified(&path) {
+                Some(time) => {
+                    if oldest_target.map(|v| time < v).unwrap_or(true) {
+                        oldest_target = Some(time);
+                    }
+                }
+                None => return false,
+            }
+        }
+        let Some(oldest_target) = oldest_target else {
+            return false;
+        };
+        let mut files = vec![];
+        for pattern in last_cmd.meta_sources() {
+            for path in expand_glob(self.runtime, &cwd, &pattern) {
+                collect_files(self.runtime, &path, &mut files);
+            }
+        }
+        files.iter().all(|path| {
+            self.runtime
+                .modified(path)
+                .map(|time| time < oldest_target)
+                .unwrap_or(true)
+        })
+    }
+
+    #[cfg(feature = "eval")]
+    fn render_up_to_date(&self, name: &str) -> String {
+        format_message(self.locale.messages().up_to_date, &[("name", name)])
+    }
+
+    #[cfg(feature = "eval")]
+    fn deprecated_warnings(&self) -> Vec<ArgcValue> {
+        let messages = self.locale.messages();
+        let mut output = vec![];
+        let mut warn = |target: &str, name: &str, message: &str| {
+            let name = format_message(target, &[("name", name)]);
+            output.push(ArgcValue::Warning(render_deprecated(
+                messages, &name, message,
+            )));
+        };
+        for (level, cmd) in self.cmds.iter().enumerate() {
+            if level > 0 {
+                let name = self.args[self.cmd_arg_indexes[level]].as_str();
+                if cmd.list_names().iter().any(|v| v == name) {
+                    if let Some(message) = &cmd.deprecated {
+                        warn(messages.command_target, &cmd.cmd_name(), message);
+                    } else if let Some(message) = cmd.deprecated_aliases.get(name) {
+                        warn(messages.alias_target, name, message);
+                    }
+                }
+            }
+            for (_, _, id) in &self.flag_option_args[level] {
+                if let Some(param) = id.and_then(|id| cmd.find_flag_option(id)) {
+                    if let Some(message) = param.deprecated() {
+                        warn(messages.argument_target, &param.long_name(), message);
+                    }
+                }
+            }
+        }
+        output
+    }
+
     #[cfg(feature = "eval")]
     pub(crate) fn to_arg_values_for_param_fn(&self) -> Vec<ArgcValue> {
         let bind_envs = self.build_bind_envs();
@@ -684,7 +816,23 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
                         check_flag_option_bind_envs.swap_remove(name);
                         flag_option_map.entry(name).or_insert(vec![]).push(i);
                     }
-                    None => return Some(MatchError::UnknownArgument(level, key.to_string())),
+                    None => {
+                        let names = cmd.list_flag_option_names();
+                        return Some(MatchError::UnknownArgument {
+                            level,
+                            arg: key.to_string(),
+                            suggestion: did_you_mean(key, &names).map(|v| v.to_string()),
+                        });
+                    }
+                }
+            }
+            let mut provided_flag_options: HashSet<&str> =
+                flag_option_map.keys().copied().collect();
+            for (name, values) in flag_option_bind_envs.iter() {
+                if let Some(param) = cmd.flag_option_params.iter().find(|v| v.id() == *name) {
+                    if param.is_option() || is_true_value(values[0]) {
+                        provided_flag_options.insert(name);
+                    }
                 }
             }
             for (name, indexes) in flag_option_map {
@@ -696,29 +844,41 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
                     let (min, _) = param.num_args();
                     for values in values_list.iter() {
                         if param.is_flag() && !values.is_empty() {
-                            return Some(MatchError::NoFlagValue(level, param.long_name()));
+                            return Some(MatchError::NoFlagValue {
+                                level,
+                                param: param.long_name(),
+                            });
                         } else if values.len() < min {
-                            return Some(MatchError::MismatchValues(
+                            return Some(MatchError::MismatchValues {
                                 level,
-                                param.render_name_notations(),
-                            ));
+                                param: param.render_name_notations(),
+                            });
                         }
                         if let Some(choices) = get_param_choice(param.choice(), &choices_fn_values)
                         {
                             for value in values.iter() {
                                 if !choices.contains(&value.to_string()) {
-                                    return Some(MatchError::InvalidValue(
+                                    return Some(MatchError::InvalidValue {
                                         level,
-                                        value.to_string(),
-                                        param.render_first_notation(),
-                                        choices.clone(),
-                                    ));
+                                        value: value.to_string(),
+                                        param: param.render_first_notation(),
+                                        choices: choices.clone(),
+                                    });
                                 }
                             }
                         }
+                        if param.is_option() {
+                            let target = format!("`{}`", param.render_first_notation());
+                            if let Some(err) = self.validate_values(level, param, values, &target) {
+                                return Some(err);
+                            }
+                        }
                     }
                     if !param.multiple_occurs() && values_list.len() > 1 {
-                        return Some(MatchError::NotMultipleArgument(level, param.long_name()));
+                        return Some(MatchError::NotMultipleArgument {
+                            level,
+                            param: param.long_name(),
+                        });
                     }
                 }
             }
@@ -737,28 +897,40 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
                         is_valid = false;
                     }
                     if !is_valid {
-                        return Some(MatchError::InvalidBindEnvironment(
+                        return Some(MatchError::InvalidBindEnvironment {
                             level,
-                            values[0].to_string(),
-                            param.bind_env().unwrap_or_default(),
-                            param.long_name(),
-                            choice_values,
-                        ));
+                            value: values[0].to_string(),
+                            env: param.bind_env().unwrap_or_default(),
+                            param: param.long_name(),
+                            choices: choice_values,
+                        });
                     }
                     if let Some(choices) = get_param_choice(param.choice(), &choices_fn_values) {
                         choice_values = choices.to_vec();
                         for value in values.iter() {
                             if !choices.contains(&value.to_string()) {
-                                return Some(MatchError::InvalidBindEnvironment(
+                                return Some(MatchError::InvalidBindEnvironment {
                                     level,
-                                    value.to_string(),
-                                    param.bind_env().unwrap_or_default(),
-                                    param.long_name(),
-                                    choice_values,
-                                ));
+                                    value: value.to_string(),
+                                    env: param.bind_env().unwrap_or_default(),
+                                    param: param.long_name(),
+                                    choices: choice_values,
+                                });
                             }
                         }
                     }
+                    if param.is_option() {
+                        let target = format_message(
+                            self.locale.messages().bind_env_target,
+                            &[
+                                ("env", &param.bind_env().unwrap_or_default()),
+                                ("name", &param.render_name_notations()),
+                            ],
+                        );
+                        if let Some(err) = self.validate_values(level, param, values, &target) {
+                            return Some(err);
+                        }
+                    }
                 }
             }
 
@@ -767,10 +939,14 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
                     .iter()
                     .filter_map(|v| cmd.find_flag_option(v).map(|v| v.render_name_notations()))
                     .collect();
-                return Some(MatchError::MissingRequiredArguments(
+                return Some(MatchError::MissingRequiredArguments {
                     level,
-                    missing_flag_options,
-                ));
+                    params: missing_flag_options,
+                });
+            }
+
+            if let Some(err) = validate_param_groups(level, cmd, &provided_flag_options) {
+                return Some(err);
             }
         }
 
@@ -783,16 +959,16 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
             }
             let name = self.positional_args[1];
             if let Some(subcmd) = last_cmd.find_subcommand(name) {
-                return Some(MatchError::DisplaySubcommandHelp(
-                    subcmd.name.clone().unwrap(),
-                ));
+                return Some(MatchError::DisplaySubcommandHelp {
+                    name: subcmd.name.clone().unwrap(),
+                });
             } else {
-                return Some(MatchError::InvalidValue(
+                return Some(MatchError::InvalidValue {
                     level,
-                    name.into(),
-                    "<command>".into(),
-                    last_cmd.list_subcommand_names(),
-                ));
+                    value: name.into(),
+                    param: "<command>".into(),
+                    choices: last_cmd.list_subcommand_names(),
+                });
             }
         }
         if !last_cmd.subcommands.is_empty() {
@@ -800,14 +976,18 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
                 if self.positional_args.is_empty() && last_args.is_empty() {
                     return Some(MatchError::DisplayHelp);
                 } else {
-                    return Some(MatchError::InvalidSubcommand(
-                        self.positional_args.first().map(|v| v.to_string()),
-                    ));
+                    return Some(MatchError::InvalidSubcommand {
+                        arg: self.positional_args.first().map(|v| v.to_string()),
+                        cmd: last_cmd.full_name(),
+                        subcommands: last_cmd.list_subcommand_names(),
+                    });
                 }
             } else if last_cmd.positional_params.is_empty() && !self.positional_args.is_empty() {
-                return Some(MatchError::InvalidSubcommand(
-                    self.positional_args.first().map(|v| v.to_string()),
-                ));
+                return Some(MatchError::InvalidSubcommand {
+                    arg: self.positional_args.first().map(|v| v.to_string()),
+                    cmd: last_cmd.full_name(),
+                    subcommands: last_cmd.list_subcommand_names(),
+                });
             }
         }
 
@@ -816,10 +996,11 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
         let positional_params_len = last_cmd.positional_params.len();
         if positional_params_len > 0 && positional_values_len > positional_params_len {
             let extra_args = &positional_values[positional_params_len];
-            return Some(MatchError::UnknownArgument(
+            return Some(MatchError::UnknownArgument {
                 level,
-                extra_args[0].to_string(),
-            ));
+                arg: extra_args[0].to_string(),
+                suggestion: None,
+            });
         }
 
         for (i, param) in last_cmd.positional_params.iter().enumerate() {
@@ -829,15 +1010,21 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
             ) {
                 for value in values.iter() {
                     if !choices.contains(&value.to_string()) {
-                        return Some(MatchError::InvalidValue(
+                        return Some(MatchError::InvalidValue {
                             level,
-                            value.to_string(),
-                            param.render_notation(),
-                            choices.clone(),
-                        ));
+                            value: value.to_string(),
+                            param: param.render_notation(),
+                            choices: choices.clone(),
+                        });
                     }
                 }
             }
+            if let Some(values) = positional_values.get(i) {
+                let target = format!("`{}`", param.render_notation());
+                if let Some(err) = self.validate_values(level, param, values, &target) {
+                    return Some(err);
+                }
+            }
         }
         if positional_params_len > positional_values_len {
             let mut missing_positionals = vec![];
@@ -846,25 +1033,35 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
                     if let Some(choices) = get_param_choice(param.choice(), &choices_fn_values) {
                         for value in values.iter() {
                             if !choices.contains(&value.to_string()) {
-                                return Some(MatchError::InvalidBindEnvironment(
+                                return Some(MatchError::InvalidBindEnvironment {
                                     level,
-                                    value.to_string(),
-                                    param.bind_env().unwrap_or_default(),
-                                    param.render_notation(),
-                                    choices.to_vec(),
-                                ));
+                                    value: value.to_string(),
+                                    env: param.bind_env().unwrap_or_default(),
+                                    param: param.render_notation(),
+                                    choices: choices.to_vec(),
+                                });
                             }
                         }
                     }
+                    let target = format_message(
+                        self.locale.messages().bind_env_target,
+                        &[
+                            ("env", &param.bind_env().unwrap_or_default()),
+                            ("name", &param.render_notation()),
+                        ],
+                    );
+                    if let Some(err) = self.validate_values(level, param, values, &target) {
+                        return Some(err);
+                    }
                 } else if param.required() {
                     missing_positionals.push(param.render_notation())
                 }
             }
             if !missing_positionals.is_empty() {
-                return Some(MatchError::MissingRequiredArguments(
+                return Some(MatchError::MissingRequiredArguments {
                     level,
-                    missing_positionals,
-                ));
+                    params: missing_positionals,
+                });
             }
         }
 
@@ -875,7 +1072,7 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
             }
         }
         if !missing_envs.is_empty() {
-            return Some(MatchError::MissingRequiredEnvironments(missing_envs));
+            return Some(MatchError::MissingRequiredEnvironments { envs: missing_envs });
         }
 
         for param in &last_cmd.env_params {
@@ -884,12 +1081,19 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
                 self.envs.get(param.id()),
             ) {
                 if !choices.contains(&value.to_string()) {
-                    return Some(MatchError::InvalidEnvironment(
+                    return Some(MatchError::InvalidEnvironment {
                         level,
-                        value.to_string(),
-                        param.id().to_string(),
-                        choices.clone(),
-                    ));
+                        value: value.to_string(),
+                        env: param.id().to_string(),
+                        choices: choices.clone(),
+                    });
+                }
+            }
+            if let Some(value) = self.envs.get(param.id()) {
+                let target =
+                    format_message(self.locale.messages().env_target, &[("name", param.id())]);
+                if let Some(err) = self.validate_values(level, param, &[value], &target) {
+                    return Some(err);
                 }
             }
         }
@@ -897,6 +1101,100 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
         None
     }
 
+    #[cfg(feature = "eval")]
+    fn validate_values<P: Param>(
+        &self,
+        level: usize,
+        param: &P,
+        values: &[&str],
+        target: &str,
+    ) -> Option<MatchError> {
+        if let Some(value_type) = param.value_type() {
+            for value in values.iter() {
+                if !self.is_type_matched(value_type, value) {
+                    return Some(MatchError::InvalidValueType {
+                        level,
+                        value: value.to_string(),
+                        target: target.to_string(),
+                        value_type,
+                    });
+                }
+            }
+        }
+        if let Some(range) = param.range() {
+            for value in values.iter() {
+                if !self.is_type_matched(ValueType::Float, value) {
+                    return Some(MatchError::InvalidValueType {
+                        level,
+                        value: value.to_string(),
+                        target: target.to_string(),
+                        value_type: ValueType::Float,
+                    });
+                }
+                if !value.parse().map(|v| range.contains(v)).unwrap_or_default() {
+                    return Some(MatchError::ValueOutOfRange {
+                        level,
+                        value: value.to_string(),
+                        target: target.to_string(),
+                        range: range.clone(),
+                    });
+                }
+            }
+        }
+        if let Some(length) = param.length() {
+            for value in values.iter() {
+                if !length.contains(value.chars().count() as f64) {
+                    return Some(MatchError::InvalidValueLength {
+                        level,
+                        value: value.to_string(),
+                        target: target.to_string(),
+                        length: length.clone(),
+                    });
+                }
+            }
+        }
+        if let Some(pattern) = param.pattern() {
+            if let Ok(re) = regex::Regex::new(pattern) {
+                for value in values.iter() {
+                    if !re.is_match(value) {
+                        return Some(MatchError::InvalidValuePattern {
+                            level,
+                            value: value.to_string(),
+                            target: target.to_string(),
+                            pattern: pattern.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    #[cfg(feature = "eval")]
+    fn is_type_matched(&self, value_type: ValueType, value: &str) -> bool {
+        match value_type {
+            ValueType::Path | ValueType::File | ValueType::Dir => {
+                let path = match self.envs.get("ARGC_PWD") {
+                    Some(cwd) => self.runtime.join_path(cwd, &[value]),
+                    None => value.to_string(),
+                };
+                match self.runtime.metadata(&path) {
+                    Some((is_dir, _, _)) => match value_type {
+                        ValueType::File => !is_dir,
+                        ValueType::Dir => is_dir,
+                        _ => true,
+                    },
+                    None => false,
+                }
+            }
+            ValueType::Regex => regex::Regex::new(value).is_ok(),
+            _ => match value_type.pattern().and_then(|v| regex::Regex::new(v).ok()) {
+                Some(re) => re.is_match(value),
+                None => true,
+            },
+        }
+    }
+
     #[cfg(feature = "eval")]
     fn execute_choices_fns<'x>(
         &'x self,
@@ -971,107 +1269,31 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
 
     #[cfg(feature = "eval")]
     fn stringify_match_error(&self, err: &MatchError) -> (String, i32) {
-        let mut exit = 0;
         let message = match err {
             MatchError::DisplayHelp => {
                 let cmd = self.last_cmd();
-                cmd.render_help(self.wrap_width)
+                cmd.render_help(
+                    self.wrap_width,
+                    self.runtime.is_color_enabled(),
+                    self.locale,
+                )
             }
-            MatchError::DisplaySubcommandHelp(name) => {
+            MatchError::DisplaySubcommandHelp { name } => {
                 let cmd = self.last_cmd();
                 let cmd = cmd.find_subcommand(name).unwrap();
-                cmd.render_help(self.wrap_width)
+                cmd.render_help(
+                    self.wrap_width,
+                    self.runtime.is_color_enabled(),
+                    self.locale,
+                )
             }
             MatchError::DisplayVersion => {
                 let cmd = self.last_cmd();
                 cmd.render_version()
             }
-            MatchError::InvalidSubcommand(arg) => {
-                exit = 1;
-                let cmd = self.last_cmd();
-                let cmd_str = cmd.cmd_paths().join("-");
-                let names = cmd.list_subcommand_names().join(", ");
-                let details = match arg {
-                    Some(arg) => format!("but '{arg}' is not one of them"),
-                    None => "but one was not provided".to_string(),
-                };
-                format!(
-                    r###"error: `{cmd_str}` requires a subcommand {details}
-  [subcommands: {names}]"###
-                )
-            }
-            MatchError::UnknownArgument(_level, name) => {
-                exit = 1;
-                format!(r###"error: unexpected argument `{name}` found"###)
-            }
-            MatchError::MissingRequiredArguments(_level, values) => {
-                exit = 1;
-                let list = values
-                    .iter()
-                    .map(|v| format!("  {v}"))
-                    .collect::<Vec<String>>()
-                    .join("\n");
-                format!(
-                    r###"error: the following required arguments were not provided:
-{list}"###
-                )
-            }
-            MatchError::MissingRequiredEnvironments(values) => {
-                exit = 1;
-                let list = values
-                    .iter()
-                    .map(|v| format!("  {v}"))
-                    .collect::<Vec<String>>()
-                    .join("\n");
-                format!(
-                    r###"error: the following required environments were not provided:
-{list}"###
-                )
-            }
-            MatchError::NotMultipleArgument(_level, name) => {
-                exit = 1;
-                format!(r###"error: the argument `{name}` cannot be used multiple times"###)
-            }
-            MatchError::InvalidValue(_level, value, name, choices) => {
-                exit = 1;
-                let list = choices.join(", ");
-                format!(
-                    r###"error: invalid value `{value}` for `{name}`
-  [possible values: {list}]"###
-                )
-            }
-            MatchError::InvalidBindEnvironment(_level, value, env_name, name, choices) => {
-                exit = 1;
-                if choices.is_empty() {
-                    format!(
-                        r###"error: environment variable `{env_name}` has invalid value for param '{name}'"###
-                    )
-                } else {
-                    let list = choices.join(", ");
-                    format!(
-                        r###"error: invalid value `{value}` for environment variable `{env_name}` that bound to `{name}`
-  [possible values: {list}]"###
-                    )
-                }
-            }
-            MatchError::InvalidEnvironment(_level, value, name, choices) => {
-                exit = 1;
-                let list = choices.join(", ");
-                format!(
-                    r###"error: invalid value `{value}` for environment variable `{name}`
-  [possible values: {list}]"###
-                )
-            }
-            MatchError::MismatchValues(_level, value) => {
-                exit = 1;
-                format!(r###"error: incorrect number of values for `{value}`"###)
-            }
-            MatchError::NoFlagValue(_level, name) => {
-                exit = 1;
-                format!(r###"error: flag `{name}` don't accept any value"###)
-            }
+            _ => err.render(self.locale.messages()),
         };
-        (message, exit)
+        (message, err.exit_code())
     }
 
     #[cfg(feature = "compgen")]
@@ -1089,7 +1311,7 @@ impl<'a, 'b, T: Runtime> Matcher<'a, 'b, T> {
         } else {
             last_cmd.all_flag_options()
         };
-        for param in params.iter() {
+        for param in params.iter().filter(|v| !v.hidden()) {
             let mut exist = args.contains(param.id());
             if !last.is_empty() && param.is_match(last) {
                 exist = false;
@@ -1353,7 +1575,7 @@ fn comp_subcomands(cmd: &Command, flag: bool) -> Vec<CompItem> {
     let mut has_help_subcmd = false;
     let mut describe_help_subcmd = false;
     let signs = cmd.flag_option_signs();
-    for subcmd in cmd.subcommands.iter() {
+    for subcmd in cmd.subcommands.iter().filter(|v| !v.hidden) {
         let describe = subcmd.describe_oneline();
         for (i, v) in subcmd.list_names().into_iter().enumerate() {
             if i > 0 && v.len() < 2 {
@@ -1493,6 +1715,62 @@ fn delimit_arg_values<'x, T: Param>(param: &T, values: &[&'x str]) -> Vec<&'x st
     }
 }
 
+#[cfg(feature = "eval")]
+fn validate_param_groups(
+    level: usize,
+    cmd: &Command,
+    provided: &HashSet<&str>,
+) -> Option<MatchError> {
+    let render_name = |id: &str| {
+        cmd.find_flag_option(id)
+            .map(|v| v.render_name_notations())
+            .unwrap_or_default()
+    };
+    for group in &cmd.param_groups {
+        match group.kind {
+            ParamGroupKind::Conflicts => {
+                let used: Vec<&String> = group
+                    .ids
+                    .iter()
+                    .filter(|v| provided.contains(v.as_str()))
+                    .collect();
+                if used.len() > 1 {
+                    return Some(MatchError::ConflictArguments {
+                        level,
+                        param: render_name(used[0]),
+                        other: render_name(used[1]),
+                    });
+                }
+            }
+            ParamGroupKind::Requires => {
+                if provided.contains(group.ids[0].as_str()) {
+                    let missing: Vec<String> = group.ids[1..]
+                        .iter()
+                        .filter(|v| !provided.contains(v.as_str()))
+                        .map(|v| render_name(v))
+                        .collect();
+                    if !missing.is_empty() {
+                        return Some(MatchError::MissingDependentArguments {
+                            level,
+                            param: render_name(&group.ids[0]),
+                            requires: missing,
+                        });
+                    }
+                }
+            }
+            ParamGroupKind::OneOfRequired => {
+                if !group.ids.iter().any(|v| provided.contains(v.as_str())) {
+                    return Some(MatchError::MissingOneOfArguments {
+                        level,
+                        params: group.ids.iter().map(|v| render_name(v)).collect(),
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
 fn is_bool_value(value: &str) -> bool {
     matches!(value, "true" | "false" | "0" | "1")
 }
@@ -1,10 +1,11 @@
+use crate::locale::{format_message, Messages};
 use crate::parser::{is_choice_value_terminate, is_default_value_terminate};
 use crate::utils::{argc_var_name, escape_shell_words, sanitize_var_name, to_cobol_case, MAX_ARGS};
 use crate::ArgcValue;
 
 use anyhow::{bail, Result};
 use indexmap::IndexMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 pub(crate) trait Param {
     fn data(&self) -> &ParamData;
@@ -23,8 +24,9 @@ pub(crate) trait Param {
             None => self.describe(),
         }
     }
-    fn render_describe(&self) -> String {
-        self.data().render_describe(self.describe(), self.id())
+    fn render_describe(&self, messages: &Messages) -> String {
+        self.data()
+            .render_describe(self.describe(), self.id(), messages)
     }
     fn describe(&self) -> &str {
         &self.data().describe
@@ -62,6 +64,27 @@ pub(crate) trait Param {
     fn bind_env(&self) -> Option<String> {
         self.data().normalize_bind_env(self.id())
     }
+    fn value_type(&self) -> Option<ValueType> {
+        self.data().value_type
+    }
+    fn range(&self) -> Option<&RangeValue> {
+        self.data().range.as_ref()
+    }
+    fn length(&self) -> Option<&RangeValue> {
+        self.data().length.as_ref()
+    }
+    fn pattern(&self) -> Option<&String> {
+        self.data().pattern.as_ref()
+    }
+    fn deprecated(&self) -> Option<&String> {
+        self.data().deprecated.as_ref()
+    }
+    fn hidden(&self) -> bool {
+        self.data().hidden
+    }
+    fn heading(&self) -> Option<&String> {
+        self.data().heading.as_ref()
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -75,6 +98,7 @@ pub(crate) struct FlagOptionParam {
     raw_notations: Vec<String>,
     notations: Vec<String>,
     inherited: bool,
+    force_override: bool,
 }
 
 impl Param for FlagOptionParam {
@@ -103,6 +127,7 @@ impl Param for FlagOptionParam {
     }
 
     fn guard(&self) -> Result<()> {
+        self.data.guard()?;
         if self.notations.len() > 1 {
             if self.assigned {
                 bail!("cannot combine assign and multiple notations")
@@ -142,6 +167,10 @@ impl Param for FlagOptionParam {
             self.data.render_source_of_name_value(&name_suffix)
         ));
 
+        if let Some(constraints) = self.data.render_source_of_constraints() {
+            output.push(constraints);
+        }
+
         if let Some(env) = &self.data.env {
             match env {
                 Some(v) => output.push(format!("${v}")),
@@ -210,6 +239,7 @@ impl FlagOptionParam {
             raw_notations,
             notations,
             inherited: false,
+            force_override: false,
         }
     }
 
@@ -232,9 +262,71 @@ impl FlagOptionParam {
             assigned: self.assigned,
             default: self.data().default.clone(),
             choice: self.data().choice.clone(),
+            value_type: self.value_type(),
+            range: self.range().cloned(),
+            length: self.length().cloned(),
+            pattern: self.pattern().cloned(),
             env: self.bind_env(),
             inherited: self.inherited,
+            deprecated: self.deprecated().cloned(),
+            hidden: self.hidden(),
+            heading: self.heading().cloned(),
+        }
+    }
+
+    #[cfg(feature = "export")]
+    pub(crate) fn import(value: &FlagOptionValue) -> Self {
+        let long_prefix: String = value
+            .long_name
+            .chars()
+            .take_while(|v| matches!(v, '-' | '+'))
+            .collect();
+        let name = &value.long_name[long_prefix.len()..];
+        let mut data_name = name.to_string();
+        if value.prefixed {
+            data_name.push('-');
+        }
+        if value.assigned {
+            data_name.push(':');
+        }
+        let data = ParamData {
+            describe: value.describe.clone(),
+            choice: value.choice.clone(),
+            default: value.default.clone(),
+            modifier: Modifier::import(
+                value.required,
+                value.multiple_occurs,
+                value.delimiter,
+                value.terminated,
+            ),
+            value_type: value.value_type,
+            range: value.range.clone(),
+            length: value.length.clone(),
+            pattern: value.pattern.clone(),
+            deprecated: value.deprecated.clone(),
+            hidden: value.hidden,
+            heading: value.heading.clone(),
+            ..ParamData::new(&data_name)
+        };
+        let mut notations = value.notations.clone();
+        if value.terminated {
+            if let Some(last) = notations.last_mut() {
+                *last = last.trim_end_matches('~').to_string();
+            }
+        }
+        if notations == [to_cobol_case(name)] {
+            notations.clear();
         }
+        let notations: Vec<&str> = notations.iter().map(|v| v.as_str()).collect();
+        let mut param = Self::new(
+            data,
+            value.flag,
+            value.short_name.as_deref(),
+            &long_prefix,
+            &notations,
+        );
+        param.data.env = ParamData::import_bind_env(value.env.as_deref(), &param.id);
+        param
     }
 
     pub(crate) fn is_flag(&self) -> bool {
@@ -432,6 +524,11 @@ impl FlagOptionParam {
         self.inherited = true;
     }
 
+    /// Whether this is the `--force` flag generated for `@meta targets`
+    pub(crate) fn is_force_override(&self) -> bool {
+        self.force_override
+    }
+
     pub(crate) fn create_help_flag(short: Option<&str>, long_prefix: &str, describe: &str) -> Self {
         let mut param_data = ParamData::new("help");
         param_data.describe = describe.to_string();
@@ -448,6 +545,14 @@ impl FlagOptionParam {
         FlagOptionParam::new(param_data, true, short, long_prefix, &[])
     }
 
+    pub(crate) fn create_force_flag(long_prefix: &str, describe: &str) -> Self {
+        let mut param_data = ParamData::new("force");
+        param_data.describe = describe.to_string();
+        let mut param = FlagOptionParam::new(param_data, true, None, long_prefix, &[]);
+        param.force_override = true;
+        param
+    }
+
     pub(crate) fn match_prefix<'a>(&self, arg: &'a str) -> Option<&'a str> {
         if self.prefixed {
             self.list_names().iter().find_map(|v| {
@@ -484,7 +589,7 @@ impl FlagOptionParam {
 }
 
 #[cfg(feature = "export")]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct FlagOptionValue {
     pub id: String,
     pub long_name: String,
@@ -502,8 +607,15 @@ pub struct FlagOptionValue {
     pub assigned: bool,
     pub default: Option<DefaultValue>,
     pub choice: Option<ChoiceValue>,
+    pub value_type: Option<ValueType>,
+    pub range: Option<RangeValue>,
+    pub length: Option<RangeValue>,
+    pub pattern: Option<String>,
     pub env: Option<String>,
     pub inherited: bool,
+    pub deprecated: Option<String>,
+    pub hidden: bool,
+    pub heading: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -535,7 +647,7 @@ impl Param for PositionalParam {
     }
 
     fn guard(&self) -> Result<()> {
-        Ok(())
+        self.data.guard()
     }
 
     fn multiple_values(&self) -> bool {
@@ -545,6 +657,10 @@ impl Param for PositionalParam {
     fn render_source(&self) -> String {
         let mut output = vec![self.data.render_source_of_name_value("")];
 
+        if let Some(constraints) = self.data.render_source_of_constraints() {
+            output.push(constraints);
+        }
+
         if let Some(env) = &self.data.env {
             match env {
                 Some(v) => output.push(format!("${v}")),
@@ -586,10 +702,43 @@ impl PositionalParam {
             terminated: self.terminated(),
             default: self.data().default.clone(),
             choice: self.data().choice.clone(),
+            value_type: self.value_type(),
+            range: self.range().cloned(),
+            length: self.length().cloned(),
+            pattern: self.pattern().cloned(),
             env: self.bind_env(),
+            hidden: self.hidden(),
         }
     }
 
+    #[cfg(feature = "export")]
+    pub(crate) fn import(value: &PositionalValue) -> Self {
+        let data = ParamData {
+            describe: value.describe.clone(),
+            choice: value.choice.clone(),
+            default: value.default.clone(),
+            modifier: Modifier::import(
+                value.required,
+                value.multiple && !value.terminated,
+                value.delimiter,
+                value.terminated,
+            ),
+            env: ParamData::import_bind_env(value.env.as_deref(), &value.id),
+            value_type: value.value_type,
+            range: value.range.clone(),
+            length: value.length.clone(),
+            pattern: value.pattern.clone(),
+            hidden: value.hidden,
+            ..ParamData::new(&value.id)
+        };
+        let raw_notation = if value.notation == to_cobol_case(&value.id) {
+            None
+        } else {
+            Some(value.notation.as_str())
+        };
+        Self::new(data, raw_notation)
+    }
+
     pub(crate) fn notation(&self) -> &str {
         &self.notation
     }
@@ -627,7 +776,7 @@ impl PositionalParam {
 }
 
 #[cfg(feature = "export")]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PositionalValue {
     pub id: String,
     pub describe: String,
@@ -638,7 +787,12 @@ pub struct PositionalValue {
     pub terminated: bool,
     pub default: Option<DefaultValue>,
     pub choice: Option<ChoiceValue>,
+    pub value_type: Option<ValueType>,
+    pub range: Option<RangeValue>,
+    pub length: Option<RangeValue>,
+    pub pattern: Option<String>,
     pub env: Option<String>,
+    pub hidden: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -669,6 +823,7 @@ impl Param for EnvParam {
     }
 
     fn guard(&self) -> Result<()> {
+        self.data.guard()?;
         if !matches!(
             self.data().modifier,
             Modifier::Optional | Modifier::Required
@@ -687,6 +842,9 @@ impl Param for EnvParam {
 
     fn render_source(&self) -> String {
         let mut output = vec![self.data.render_source_of_name_value("")];
+        if let Some(constraints) = self.data.render_source_of_constraints() {
+            output.push(constraints);
+        }
         if let Some(env) = &self.data.env {
             match env {
                 Some(v) => output.push(format!("${v}")),
@@ -716,10 +874,32 @@ impl EnvParam {
             required: self.required(),
             default: self.data().default.clone(),
             choice: self.data().choice.clone(),
+            value_type: self.value_type(),
+            range: self.range().cloned(),
+            length: self.length().cloned(),
+            pattern: self.pattern().cloned(),
             inherited: self.inherited,
+            hidden: self.hidden(),
         }
     }
 
+    #[cfg(feature = "export")]
+    pub(crate) fn import(value: &EnvValue) -> Self {
+        let data = ParamData {
+            describe: value.describe.clone(),
+            choice: value.choice.clone(),
+            default: value.default.clone(),
+            modifier: Modifier::import(value.required, false, None, false),
+            value_type: value.value_type,
+            range: value.range.clone(),
+            length: value.length.clone(),
+            pattern: value.pattern.clone(),
+            hidden: value.hidden,
+            ..ParamData::new(&value.id)
+        };
+        Self::new(data)
+    }
+
     pub(crate) fn render_body(&self) -> String {
         let marker = if self.required() { "*" } else { "" };
         format!("{}{}", self.id(), marker)
@@ -741,14 +921,19 @@ impl EnvParam {
 }
 
 #[cfg(feature = "export")]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct EnvValue {
     pub id: String,
     pub describe: String,
     pub required: bool,
     pub default: Option<DefaultValue>,
     pub choice: Option<ChoiceValue>,
+    pub value_type: Option<ValueType>,
+    pub range: Option<RangeValue>,
+    pub length: Option<RangeValue>,
+    pub pattern: Option<String>,
     pub inherited: bool,
+    pub hidden: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -759,6 +944,13 @@ pub(crate) struct ParamData {
     pub(crate) default: Option<DefaultValue>,
     pub(crate) modifier: Modifier,
     pub(crate) env: Option<Option<String>>,
+    pub(crate) value_type: Option<ValueType>,
+    pub(crate) range: Option<RangeValue>,
+    pub(crate) length: Option<RangeValue>,
+    pub(crate) pattern: Option<String>,
+    pub(crate) deprecated: Option<String>,
+    pub(crate) hidden: bool,
+    pub(crate) heading: Option<String>,
 }
 
 impl ParamData {
@@ -770,9 +962,40 @@ impl ParamData {
             default: None,
             modifier: Modifier::Optional,
             env: None,
+            value_type: None,
+            range: None,
+            length: None,
+            pattern: None,
+            deprecated: None,
+            hidden: false,
+            heading: None,
         }
     }
 
+    pub(crate) fn guard(&self) -> Result<()> {
+        // A `{..}` left at the head of the description made of constraint-like items is a malformed
+        // annotation, anything else such as `{json}` is part of the description
+        if let Some(word) = self.describe.split_whitespace().next() {
+            if let Some(inner) = word.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+                let is_constraint = |item: &str| {
+                    ValueType::from_name(item).is_some()
+                        || item.starts_with("len=")
+                        || item.contains("..")
+                };
+                if !inner.is_empty() && inner.split(',').all(is_constraint) {
+                    bail!("unsupported value constraint `{word}`")
+                }
+            }
+        }
+        #[cfg(feature = "eval")]
+        if let Some(pattern) = &self.pattern {
+            if regex::Regex::new(pattern).is_err() {
+                bail!("invalid pattern `{pattern}`")
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn required(&self) -> bool {
         self.modifier.required() && self.default.is_none()
     }
@@ -828,6 +1051,17 @@ impl ParamData {
         Some(env)
     }
 
+    /// Reverse of `normalize_bind_env`, use `$$` if the env name can be derived from the id.
+    #[cfg(feature = "export")]
+    pub(crate) fn import_bind_env(env: Option<&str>, id: &str) -> Option<Option<String>> {
+        let env = env?;
+        if env == sanitize_var_name(id).to_uppercase() {
+            Some(None)
+        } else {
+            Some(Some(env.to_string()))
+        }
+    }
+
     #[allow(unused)]
     pub(crate) fn render_source_of_name_value(&self, name_suffix: &str) -> String {
         let mut output = format!("{}{name_suffix}", self.name);
@@ -851,10 +1085,32 @@ impl ParamData {
             }
             _ => {}
         }
+        if let Some(pattern) = &self.pattern {
+            output.push_str(&format!("[/{pattern}/]"));
+        }
         output
     }
 
-    pub(crate) fn render_describe(&self, describe: &str, id: &str) -> String {
+    #[allow(unused)]
+    pub(crate) fn render_source_of_constraints(&self) -> Option<String> {
+        let mut list = vec![];
+        if let Some(value_type) = &self.value_type {
+            list.push(value_type.name().to_string());
+        }
+        if let Some(range) = &self.range {
+            list.push(range.to_string());
+        }
+        if let Some(length) = &self.length {
+            list.push(format!("len={length}"));
+        }
+        if list.is_empty() {
+            None
+        } else {
+            Some(format!("{{{}}}", list.join(",")))
+        }
+    }
+
+    pub(crate) fn render_describe(&self, describe: &str, id: &str, messages: &Messages) -> String {
         let mut output = describe.to_string();
         let multiline = describe.contains('\n');
         let mut documented = false;
@@ -866,7 +1122,10 @@ impl ParamData {
                     output.push(sep)
                 }
                 let values: Vec<String> = values.iter().map(|v| escape_shell_words(v)).collect();
-                output.push_str(&format!("[possible values: {}]", values.join(", ")));
+                output.push_str(&format_message(
+                    messages.possible_values,
+                    &[("values", &values.join(", "))],
+                ));
             }
         }
         if !documented {
@@ -874,14 +1133,56 @@ impl ParamData {
                 if !output.is_empty() {
                     output.push(sep)
                 }
-                output.push_str(&format!("[default: {}]", escape_shell_words(value)));
+                output.push_str(&format_message(
+                    messages.default_value,
+                    &[("value", &escape_shell_words(value))],
+                ));
+            }
+        }
+        if let Some(value_type) = &self.value_type {
+            if !output.is_empty() {
+                output.push(sep)
+            }
+            output.push_str(&format_message(
+                messages.value_type,
+                &[("name", value_type.name())],
+            ));
+        }
+        if let Some(range) = &self.range {
+            if !output.is_empty() {
+                output.push(sep)
+            }
+            output.push_str(&format_message(
+                messages.range,
+                &[("range", &range.to_string())],
+            ));
+        }
+        if let Some(length) = &self.length {
+            if !output.is_empty() {
+                output.push(sep)
             }
+            output.push_str(&format_message(
+                messages.length,
+                &[("length", &length.to_string())],
+            ));
+        }
+        if let Some(pattern) = &self.pattern {
+            if !output.is_empty() {
+                output.push(sep)
+            }
+            output.push_str(&format_message(messages.pattern, &[("pattern", pattern)]));
         }
         if let Some(env) = self.normalize_bind_env(id) {
             if !describe.contains(&env) && !output.is_empty() {
                 output.push(sep)
             }
-            output.push_str(&format!("[env: {env}]"));
+            output.push_str(&format_message(messages.env, &[("name", &env)]));
+        }
+        if self.deprecated.is_some() {
+            if !output.is_empty() {
+                output.push(sep)
+            }
+            output.push_str(messages.deprecated);
         }
         output
     }
@@ -946,6 +1247,24 @@ impl Modifier {
         }
     }
 
+    #[cfg(feature = "export")]
+    pub(crate) fn import(
+        required: bool,
+        multiple: bool,
+        delimiter: Option<char>,
+        terminated: bool,
+    ) -> Self {
+        match (terminated, delimiter, multiple, required) {
+            (true, _, _, _) => Self::Terminated,
+            (_, Some(c), _, true) => Self::DelimiterRequired(c),
+            (_, Some(c), _, false) => Self::DelimiterOptional(c),
+            (_, None, true, true) => Self::MultipleRequired,
+            (_, None, true, false) => Self::MultipleOptional,
+            (_, None, false, true) => Self::Required,
+            (_, None, false, false) => Self::Optional,
+        }
+    }
+
     #[allow(unused)]
     pub(crate) fn render(&self) -> String {
         match self {
@@ -960,20 +1279,167 @@ impl Modifier {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
 pub enum ChoiceValue {
     Values(Vec<String>),
     Fn(String, bool),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "value")]
 pub enum DefaultValue {
     Value(String),
     Fn(String),
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueType {
+    Int,
+    Float,
+    Bool,
+    Path,
+    File,
+    Dir,
+    Url,
+    Regex,
+}
+
+impl ValueType {
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        let value = match name {
+            "int" => Self::Int,
+            "float" => Self::Float,
+            "bool" => Self::Bool,
+            "path" => Self::Path,
+            "file" => Self::File,
+            "dir" => Self::Dir,
+            "url" => Self::Url,
+            "regex" => Self::Regex,
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Int => "int",
+            Self::Float => "float",
+            Self::Bool => "bool",
+            Self::Path => "path",
+            Self::File => "file",
+            Self::Dir => "dir",
+            Self::Url => "url",
+            Self::Regex => "regex",
+        }
+    }
+
+    /// Used in error messages, e.g. `[expected: integer]`
+    pub(crate) fn expected(&self) -> &'static str {
+        match self {
+            Self::Int => "integer",
+            Self::Float => "number",
+            Self::Bool => "boolean",
+            Self::Path => "existing path",
+            Self::File => "existing file",
+            Self::Dir => "existing directory",
+            Self::Url => "url",
+            Self::Regex => "regular expression",
+        }
+    }
+
+    /// Keep in sync with `_argc_validate_type` in build.rs
+    pub(crate) fn pattern(&self) -> Option<&'static str> {
+        match self {
+            Self::Int => Some(r"^[+-]?[0-9]+$"),
+            Self::Float => Some(r"^[+-]?([0-9]+(\.[0-9]*)?|\.[0-9]+)([eE][+-]?[0-9]+)?$"),
+            Self::Bool => Some(r"^(true|false|0|1)$"),
+            Self::Url => Some(r"^[A-Za-z][A-Za-z0-9+.-]*://[^[:space:]]+$"),
+            _ => None,
+        }
+    }
+}
+
+/// Bounds of a `{1..=10}` or `{len=..64}` constraint, never NaN so that it is `Eq`
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawRangeValue")]
+pub struct RangeValue {
+    min: Option<f64>,
+    max: Option<f64>,
+    inclusive: bool,
+}
+
+impl Eq for RangeValue {}
+
+#[derive(Deserialize)]
+struct RawRangeValue {
+    min: Option<f64>,
+    max: Option<f64>,
+    inclusive: bool,
+}
+
+impl TryFrom<RawRangeValue> for RangeValue {
+    type Error = String;
+
+    fn try_from(value: RawRangeValue) -> std::result::Result<Self, Self::Error> {
+        Self::new(value.min, value.max, value.inclusive).ok_or_else(|| "invalid range".into())
+    }
+}
+
+impl RangeValue {
+    /// None if a bound is NaN or infinite
+    pub fn new(min: Option<f64>, max: Option<f64>, inclusive: bool) -> Option<Self> {
+        if [min, max].into_iter().flatten().any(|v| !v.is_finite()) {
+            return None;
+        }
+        Some(Self {
+            min,
+            max,
+            inclusive,
+        })
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.max
+    }
+
+    pub fn inclusive(&self) -> bool {
+        self.inclusive
+    }
+
+    #[cfg(feature = "eval")]
+    pub(crate) fn contains(&self, value: f64) -> bool {
+        if let Some(min) = self.min {
+            if value < min {
+                return false;
+            }
+        }
+        match self.max {
+            Some(max) if self.inclusive => value <= max,
+            Some(max) => value < max,
+            None => true,
+        }
+    }
+}
+
+impl std::fmt::Display for RangeValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(min) = self.min {
+            write!(f, "{min}")?;
+        }
+        f.write_str(if self.inclusive { "..=" } else { ".." })?;
+        if let Some(max) = self.max {
+            write!(f, "{max}")?;
+        }
+        Ok(())
+    }
+}
+
 fn must_get_first(value: &[&str]) -> String {
     if value.is_empty() {
         String::new()
@@ -0,0 +1,170 @@
+use crate::argc_value::{ArgcValue, MatchError};
+
+use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
+use std::{fmt, str::FromStr};
+
+/// Arguments matched against an argc script, for Rust programs that use the script as their CLI definition.
+///
+/// Values are looked up by param id. Defaults given by a function need bash to be evaluated,
+/// so they are left out.
+#[derive(Debug, Default, PartialEq)]
+pub struct ParsedArgs {
+    command_path: Vec<String>,
+    command_fn: Option<String>,
+    values: IndexMap<String, Vec<String>>,
+    maps: IndexMap<String, IndexMap<String, Vec<String>>>,
+    positionals: Vec<String>,
+    envs: IndexMap<String, String>,
+}
+
+impl ParsedArgs {
+    pub(crate) fn new(command_path: Vec<String>, command_fn: Option<String>) -> Self {
+        Self {
+            command_path,
+            command_fn,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn add_value(&mut self, value: ArgcValue) {
+        match value {
+            ArgcValue::Single(id, value) if !id.starts_with('_') => {
+                self.values.insert(id, vec![value]);
+            }
+            ArgcValue::Multiple(id, values) if !id.starts_with('_') => {
+                self.values.insert(id, values);
+            }
+            ArgcValue::Map(id, map) => {
+                self.maps.insert(id, map);
+            }
+            ArgcValue::PositionalSingle(id, value) => {
+                self.positionals.push(value.clone());
+                self.values.insert(id, vec![value]);
+            }
+            ArgcValue::PositionalMultiple(id, values) => {
+                self.positionals.extend(values.iter().cloned());
+                self.values.insert(id, values);
+            }
+            ArgcValue::ExtraPositionalMultiple(values) => {
+                self.positionals.extend(values);
+            }
+            ArgcValue::Env(name, value) => {
+                self.envs.insert(name, value);
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn add_env(&mut self, name: &str, value: &str) {
+        self.envs.insert(name.to_string(), value.to_string());
+    }
+
+    /// Names of the subcommands leading to the matched command, empty for the root command
+    pub fn command_path(&self) -> &[String] {
+        &self.command_path
+    }
+
+    /// Function the script would run, if any
+    pub fn command_fn(&self) -> Option<&str> {
+        self.command_fn.as_deref()
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.values.contains_key(id) || self.maps.contains_key(id)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.values
+            .get(id)
+            .and_then(|v| v.first())
+            .map(|v| v.as_str())
+    }
+
+    pub fn get_many(&self, id: &str) -> Option<&[String]> {
+        self.values.get(id).map(|v| v.as_slice())
+    }
+
+    /// Values of a prefixed option such as `-D-*`, keyed by the suffix
+    pub fn get_map(&self, id: &str) -> Option<&IndexMap<String, Vec<String>>> {
+        self.maps.get(id)
+    }
+
+    pub fn get_flag(&self, id: &str) -> bool {
+        self.get_count(id) > 0
+    }
+
+    /// How many times a flag occurs
+    pub fn get_count(&self, id: &str) -> usize {
+        self.get(id)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn get_parsed<T>(&self, id: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.get(id).map(|v| parse_value(id, v)).transpose()
+    }
+
+    pub fn get_parsed_many<T>(&self, id: &str) -> Result<Option<Vec<T>>>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.get_many(id)
+            .map(|values| values.iter().map(|v| parse_value(id, v)).collect())
+            .transpose()
+    }
+
+    /// All positional arguments of the matched command
+    pub fn positionals(&self) -> &[String] {
+        &self.positionals
+    }
+
+    /// Value of an `@env` variable, either from the environment or its default
+    pub fn get_env(&self, name: &str) -> Option<&str> {
+        self.envs.get(name).map(|v| v.as_str())
+    }
+}
+
+fn parse_value<T>(id: &str, value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|err| anyhow!("invalid value `{value}` for `{id}`, {err}"))
+}
+
+/// Error of `parse`.
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The script isn't a valid argc script
+    InvalidScript(String),
+    /// No args were given, the first arg must be the command name
+    EmptyArgs,
+    /// The args don't match the script, including the requests for help or version
+    #[non_exhaustive]
+    Match {
+        kind: Box<MatchError>,
+        exit: i32,
+        message: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidScript(message) => write!(f, "{message}"),
+            ParseError::EmptyArgs => write!(f, "No args, expected the command name"),
+            ParseError::Match { message, .. } => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -1,18 +1,20 @@
 use crate::param::{
     ChoiceValue, DefaultValue, EnvParam, FlagOptionParam, Modifier, Param, ParamData,
-    PositionalParam,
+    PositionalParam, RangeValue, ValueType,
 };
 use crate::utils::is_special_var_char;
 use crate::Result;
 use anyhow::bail;
 use nom::{
     branch::alt,
-    bytes::complete::{escaped, tag, take_till, take_while1},
+    bytes::complete::{escaped, tag, take_till, take_until, take_while1},
     character::{
-        complete::{anychar, char, one_of, satisfy, space0, space1},
+        complete::{anychar, char, digit1, one_of, satisfy, space0, space1},
         streaming::none_of,
     },
-    combinator::{eof, fail, map, not, opt, peek, rest, success},
+    combinator::{
+        eof, fail, map, map_opt, map_res, not, opt, peek, recognize, rest, success, verify,
+    },
     error::ErrorKind,
     multi::{many0, many1, separated_list1},
     sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
@@ -25,6 +27,13 @@ pub(crate) struct Event {
 
 pub(crate) type Position = usize;
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum ValueConstraint {
+    Type(ValueType),
+    Range(RangeValue),
+    Length(RangeValue),
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub(crate) enum EventData {
     /// Description
@@ -39,6 +48,14 @@ pub(crate) enum EventData {
     Cmd(String),
     /// Define alias for a subcommand, e.g. `@alias t,tst`
     Aliases(Vec<String>),
+    /// Mark the preceding cmd/alias/flag/option as deprecated, e.g. `@deprecated Use --bar`
+    Deprecated(String),
+    /// Hide the preceding cmd/flag/option/arg/env from help and completions, e.g. `@hidden`
+    Hidden,
+    /// Group the following cmds/flags/options under a heading, e.g. `@heading Advanced`
+    Heading(String),
+    /// Add an example, the first line is the command and the rest is its description, e.g. `@example prog build --release`
+    Example(String),
     /// Define a env param
     Env(EnvParam),
     /// Define a flag or option parameter
@@ -51,19 +68,14 @@ pub(crate) enum EventData {
     Unknown(String),
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Default)]
 pub(crate) enum EventScope {
+    #[default]
     Root,
     CmdStart,
     FnEnd,
 }
 
-impl Default for EventScope {
-    fn default() -> Self {
-        Self::Root
-    }
-}
-
 /// Tokenize shell script
 pub(crate) fn parse(source: &str) -> Result<Vec<Event>> {
     let mut result = vec![];
@@ -85,6 +97,10 @@ pub(crate) fn parse(source: &str) -> Result<Vec<Event>> {
                                 line_idx += take_comment_lines(&lines, line_idx + 1, &mut text);
                                 EventData::Cmd(text)
                             }
+                            EventData::Example(mut text) => {
+                                line_idx += take_comment_lines(&lines, line_idx + 1, &mut text);
+                                EventData::Example(text)
+                            }
                             EventData::Env(mut param) => {
                                 line_idx +=
                                     take_comment_lines(&lines, line_idx + 1, param.describe_mut());
@@ -147,6 +163,7 @@ fn parse_tag(input: &str) -> nom::IResult<&str, Option<EventData>> {
         tuple((many1(char('#')), space0, char('@'))),
         alt((
             parse_tag_text,
+            parse_tag_hidden,
             parse_tag_meta,
             parse_tag_param,
             parse_tag_alias,
@@ -158,7 +175,15 @@ fn parse_tag(input: &str) -> nom::IResult<&str, Option<EventData>> {
 fn parse_tag_text(input: &str) -> nom::IResult<&str, Option<EventData>> {
     map(
         pair(
-            alt((tag("describe"), tag("version"), tag("author"), tag("cmd"))),
+            alt((
+                tag("describe"),
+                tag("version"),
+                tag("author"),
+                tag("cmd"),
+                tag("deprecated"),
+                tag("heading"),
+                tag("example"),
+            )),
             parse_tail,
         ),
         |(tag, text)| {
@@ -168,12 +193,21 @@ fn parse_tag_text(input: &str) -> nom::IResult<&str, Option<EventData>> {
                 "version" => EventData::Version(text),
                 "author" => EventData::Author(text),
                 "cmd" => EventData::Cmd(text),
+                "deprecated" => EventData::Deprecated(text),
+                "heading" => EventData::Heading(text),
+                "example" => EventData::Example(text),
                 _ => unreachable!(),
             })
         },
     )(input)
 }
 
+fn parse_tag_hidden(input: &str) -> nom::IResult<&str, Option<EventData>> {
+    map(terminated(tag("hidden"), pair(space0, eof)), |_| {
+        Some(EventData::Hidden)
+    })(input)
+}
+
 fn parse_tag_meta(input: &str) -> nom::IResult<&str, Option<EventData>> {
     preceded(
         tag("meta"),
@@ -233,6 +267,7 @@ fn parse_with_long_option_param(input: &str) -> nom::IResult<&str, FlagOptionPar
         tuple((
             parse_with_long_head,
             alt((
+                parse_param_modifier_pattern,
                 parse_param_modifier_choices_default,
                 parse_param_modifier_choices_fn,
                 parse_param_modifier_choices,
@@ -240,11 +275,23 @@ fn parse_with_long_option_param(input: &str) -> nom::IResult<&str, FlagOptionPar
                 parse_param_assign,
                 parse_param_modifier,
             )),
+            parse_zero_or_one_value_constraints,
             parse_zero_or_one_bind_env,
             parse_zero_or_many_value_notations,
+            parse_zero_or_one_value_constraints,
             parse_tail,
         )),
-        |((short, long_prefix), mut arg, env, value_names, describe)| {
+        |(
+            (short, long_prefix),
+            mut arg,
+            constraints,
+            env,
+            value_names,
+            tail_constraints,
+            describe,
+        )| {
+            apply_value_constraints(&mut arg, constraints);
+            apply_value_constraints(&mut arg, tail_constraints);
             arg.env = env;
             arg.describe = describe.to_string();
             FlagOptionParam::new(arg, false, short, long_prefix, &value_names)
@@ -260,6 +307,7 @@ fn parse_no_long_option_param(input: &str) -> nom::IResult<&str, FlagOptionParam
             preceded(
                 verify_single_char,
                 alt((
+                    parse_param_modifier_pattern,
                     parse_param_modifier_choices_default,
                     parse_param_modifier_choices_fn,
                     parse_param_modifier_choices,
@@ -268,11 +316,15 @@ fn parse_no_long_option_param(input: &str) -> nom::IResult<&str, FlagOptionParam
                     parse_param_modifier,
                 )),
             ),
+            parse_zero_or_one_value_constraints,
             parse_zero_or_one_bind_env,
             parse_zero_or_many_value_notations,
+            parse_zero_or_one_value_constraints,
             parse_tail,
         )),
-        |(long_prefix, mut arg, env, value_names, describe)| {
+        |(long_prefix, mut arg, constraints, env, value_names, tail_constraints, describe)| {
+            apply_value_constraints(&mut arg, constraints);
+            apply_value_constraints(&mut arg, tail_constraints);
             arg.env = env;
             arg.describe = describe.to_string();
             FlagOptionParam::new(arg, false, None, long_prefix, &value_names)
@@ -283,8 +335,9 @@ fn parse_no_long_option_param(input: &str) -> nom::IResult<&str, FlagOptionParam
 // Parse `@env`
 fn parse_env_param(input: &str) -> nom::IResult<&str, EnvParam> {
     map(
-        pair(
+        tuple((
             alt((
+                parse_param_modifier_pattern,
                 parse_param_modifier_choices_default,
                 parse_param_modifier_choices_fn,
                 parse_param_modifier_choices,
@@ -292,9 +345,11 @@ fn parse_env_param(input: &str) -> nom::IResult<&str, EnvParam> {
                 parse_param_assign,
                 parse_param_modifier,
             )),
+            parse_zero_or_one_value_constraints,
             parse_tail,
-        ),
-        |(mut arg, describe)| {
+        )),
+        |(mut arg, constraints, describe)| {
+            apply_value_constraints(&mut arg, constraints);
             arg.describe = describe.to_string();
             EnvParam::new(arg)
         },
@@ -306,6 +361,7 @@ fn parse_positional_param(input: &str) -> nom::IResult<&str, PositionalParam> {
     map(
         tuple((
             alt((
+                parse_param_modifier_pattern,
                 parse_param_modifier_choices_default,
                 parse_param_modifier_choices_fn,
                 parse_param_modifier_choices,
@@ -313,11 +369,15 @@ fn parse_positional_param(input: &str) -> nom::IResult<&str, PositionalParam> {
                 parse_param_assign,
                 parse_param_modifier,
             )),
+            parse_zero_or_one_value_constraints,
             parse_zero_or_one_bind_env,
             parse_zero_or_one_value_notation,
+            parse_zero_or_one_value_constraints,
             parse_tail,
         )),
-        |(mut arg, env, value_name, describe)| {
+        |(mut arg, constraints, env, value_name, tail_constraints, describe)| {
+            apply_value_constraints(&mut arg, constraints);
+            apply_value_constraints(&mut arg, tail_constraints);
             arg.env = env;
             arg.describe = describe.to_string();
             PositionalParam::new(arg, value_name)
@@ -515,6 +575,24 @@ fn parse_param_modifier_choices_fn(input: &str) -> nom::IResult<&str, ParamData>
     )(input)
 }
 
+// Parse `str[/pattern/]`
+fn parse_param_modifier_pattern(input: &str) -> nom::IResult<&str, ParamData> {
+    map(
+        pair(
+            parse_param_modifier,
+            delimited(
+                tag("[/"),
+                verify(take_until("/]"), |v: &str| !v.is_empty()),
+                tag("/]"),
+            ),
+        ),
+        |(mut arg, pattern)| {
+            arg.pattern = Some(pattern.to_string());
+            arg
+        },
+    )(input)
+}
+
 fn parse_param_name(input: &str) -> nom::IResult<&str, ParamData> {
     map(parse_name, ParamData::new)(input)
 }
@@ -537,6 +615,86 @@ fn parse_value_notation(input: &str) -> nom::IResult<&str, &str> {
     )(input)
 }
 
+// Zero or one '{int,1..=65535}'
+fn parse_zero_or_one_value_constraints(
+    input: &str,
+) -> nom::IResult<&str, Option<Vec<ValueConstraint>>> {
+    opt(parse_value_constraints)(input)
+}
+
+// Parse '{int,1..=65535}' '{len=..=64}'
+fn parse_value_constraints(input: &str) -> nom::IResult<&str, Vec<ValueConstraint>> {
+    preceded(
+        char(' '),
+        delimited(
+            char('{'),
+            separated_list1(char(','), parse_value_constraint),
+            char('}'),
+        ),
+    )(input)
+}
+
+fn parse_value_constraint(input: &str) -> nom::IResult<&str, ValueConstraint> {
+    alt((
+        map(
+            preceded(
+                tag("len="),
+                verify(parse_range, |v: &RangeValue| {
+                    [v.min(), v.max()]
+                        .into_iter()
+                        .flatten()
+                        .all(|v| v >= 0.0 && v.fract() == 0.0)
+                }),
+            ),
+            ValueConstraint::Length,
+        ),
+        map(parse_range, ValueConstraint::Range),
+        map(
+            map_opt(parse_name, ValueType::from_name),
+            ValueConstraint::Type,
+        ),
+    ))(input)
+}
+
+// Parse `1..=10` `1..10` `1..` `..10` `..=10`
+fn parse_range(input: &str) -> nom::IResult<&str, RangeValue> {
+    map_opt(
+        tuple((
+            opt(parse_number),
+            alt((tag("..="), tag(".."))),
+            opt(parse_number),
+        )),
+        |(min, op, max)| {
+            let inclusive = op == "..=";
+            if max.is_none() && (inclusive || min.is_none()) {
+                return None;
+            }
+            RangeValue::new(min, max, inclusive)
+        },
+    )(input)
+}
+
+fn parse_number(input: &str) -> nom::IResult<&str, f64> {
+    map_res(
+        recognize(tuple((
+            opt(one_of("+-")),
+            digit1,
+            opt(pair(char('.'), digit1)),
+        ))),
+        |v: &str| v.parse::<f64>(),
+    )(input)
+}
+
+fn apply_value_constraints(arg: &mut ParamData, constraints: Option<Vec<ValueConstraint>>) {
+    for constraint in constraints.unwrap_or_default() {
+        match constraint {
+            ValueConstraint::Type(v) => arg.value_type = Some(v),
+            ValueConstraint::Range(v) => arg.range = Some(v),
+            ValueConstraint::Length(v) => arg.length = Some(v),
+        }
+    }
+}
+
 fn parse_bind_env_name(input: &str) -> nom::IResult<&str, &str> {
     take_while1(is_env_name_char)(input)
 }
@@ -810,6 +968,12 @@ mod tests {
         ($comment:literal, Error) => {
             assert_eq!(parse_line($comment).unwrap().1.unwrap(), None)
         };
+        ($comment:literal, Hidden) => {
+            assert_eq!(
+                parse_line($comment).unwrap().1,
+                Some(Some(EventData::Hidden))
+            )
+        };
         ($comment:literal, $kind:ident) => {
             assert!(
                 if let Some(Some(EventData::$kind(_))) = parse_line($comment).unwrap().1 {
@@ -947,6 +1111,27 @@ mod tests {
         assert_parse_option_arg!("--foo $$");
         assert_parse_option_arg!("--foo $FOO");
         assert_parse_option_arg!("--foo $FOO <FOO>");
+        assert_parse_option_arg!("--foo {int}");
+        assert_parse_option_arg!("--foo=8080 {int} $$ <PORT>");
+        assert_parse_option_arg!("--foo*,[a|b] {url}");
+        assert_parse_option_arg!("--foo {json}");
+        assert_parse_option_arg!("--foo <NUM> {int}", "--foo {int} <NUM>");
+        assert_parse_option_arg!(
+            "--foo $$ <NUM> {1..10} Count",
+            "--foo {1..10} $$ <NUM> Count"
+        );
+        assert_parse_option_arg!("--foo {int,1..=65535}");
+        assert_parse_option_arg!("--foo {1..}");
+        assert_parse_option_arg!("--foo {..0.5}");
+        assert_parse_option_arg!("--foo {-10..=10}");
+        assert_parse_option_arg!("--foo {len=1..=64}");
+        assert_parse_option_arg!("--foo {float,0..1,len=..=8}");
+        assert_parse_option_arg!("--foo {..}");
+        assert_parse_option_arg!("--foo {1..=}");
+        assert_parse_option_arg!("--foo {len=0.5..}");
+        assert_parse_option_arg!(r"--tag[/^v\d+\.\d+$/]");
+        assert_parse_option_arg!(r"--tag*,[/^[a-z]+$/] {len=..=8}");
+        assert_parse_option_arg!("--tag[//]");
     }
 
     #[test]
@@ -1087,6 +1272,11 @@ mod tests {
         assert_parse_positional_arg!("foo*[=a|b]");
         assert_parse_positional_arg!("foo $$");
         assert_parse_positional_arg!("foo $FOO");
+        assert_parse_positional_arg!("foo {file}");
+        assert_parse_positional_arg!("foo+ {dir} $FOO <DIR>");
+        assert_parse_positional_arg!("foo <NUM> {int,1..}", "foo {int,1..} <NUM>");
+        assert_parse_positional_arg!("foo {len=..10}");
+        assert_parse_positional_arg!("foo+[/^[0-9a-f]{7,40}$/]");
     }
 
     #[test]
@@ -1104,6 +1294,12 @@ mod tests {
         assert_token!("# @cmd A subcommand", Cmd, "A subcommand");
         assert_token!("# @alias tst", Aliases, ["tst"]);
         assert_token!("# @alias t,tst", Aliases, ["t", "tst"]);
+        assert_token!("# @deprecated", Deprecated, "");
+        assert_token!("# @deprecated Use --bar", Deprecated, "Use --bar");
+        assert_token!("# @hidden", Hidden);
+        assert_token!("# @heading", Heading, "");
+        assert_token!("# @heading Advanced", Heading, "Advanced");
+        assert_token!("# @example prog --foo", Example, "prog --foo");
         assert_token!("# @flag -f --foo", FlagOption);
         assert_token!("# @option -f --foo", FlagOption);
         assert_token!("# @arg foo", Positional);
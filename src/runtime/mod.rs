@@ -2,7 +2,7 @@
 pub mod navite;
 
 use anyhow::Result;
-use std::{collections::HashMap, env};
+use std::{collections::HashMap, env, time::SystemTime};
 
 pub trait Runtime
 where
@@ -32,10 +32,34 @@ where
     fn read_dir(&self, path: &str) -> Option<Vec<String>>;
     fn read_to_string(&self, path: &str) -> Option<String>;
 
+    /// Modification time of a file, without it `@meta targets` are never up to date
+    fn modified(&self, _path: &str) -> Option<SystemTime> {
+        None
+    }
+
+    /// Contents of a file, without it `@meta cache` is skipped
+    fn read(&self, _path: &str) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Whether stdout is a terminal, without it the help is never styled
+    fn is_terminal(&self) -> bool {
+        false
+    }
+
     fn is_windows(&self) -> bool {
         self.os() == "windows"
     }
 
+    /// Whether the help can be styled with ANSI colors, any non-empty `NO_COLOR` turns it off
+    fn is_color_enabled(&self) -> bool {
+        let no_color = self
+            .env_var("NO_COLOR")
+            .map(|v| !v.is_empty())
+            .unwrap_or_default();
+        !no_color && self.is_terminal()
+    }
+
     fn shell_args(&self, shell_path: &str) -> Vec<String> {
         if let Some(name) = self.basename(shell_path).map(|v| v.to_lowercase()) {
             match name.as_str() {
@@ -98,3 +122,86 @@ where
         path_env
     }
 }
+
+#[cfg(feature = "eval")]
+/// Expand a path pattern that may contain `*`, `?` and `**` segments
+pub(crate) fn expand_glob<T: Runtime>(runtime: T, cwd: &str, pattern: &str) -> Vec<String> {
+    let (base, pattern) = match pattern.strip_prefix('/') {
+        Some(pattern) => ("/".to_string(), pattern),
+        None => (cwd.to_string(), pattern),
+    };
+    let mut paths = vec![base];
+    for part in pattern.split('/').filter(|v| !v.is_empty() && *v != ".") {
+        let mut next_paths = vec![];
+        for path in paths {
+            if part == "**" {
+                collect_dirs(runtime, &path, &mut next_paths);
+            } else if part.contains(['*', '?']) {
+                for name in runtime.read_dir(&path).unwrap_or_default() {
+                    if (!name.starts_with('.') || part.starts_with('.'))
+                        && wildcard_match(part, &name)
+                    {
+                        next_paths.push(runtime.join_path(&path, &[&name]));
+                    }
+                }
+            } else {
+                next_paths.push(runtime.join_path(&path, &[part]));
+            }
+        }
+        paths = next_paths;
+    }
+    paths.retain(|path| runtime.metadata(path).is_some());
+    paths
+}
+
+#[cfg(feature = "eval")]
+fn collect_dirs<T: Runtime>(runtime: T, path: &str, output: &mut Vec<String>) {
+    output.push(path.to_string());
+    for name in runtime.read_dir(path).unwrap_or_default() {
+        if name.starts_with('.') {
+            continue;
+        }
+        let child = runtime.join_path(path, &[&name]);
+        if let Some((true, _, _)) = runtime.metadata(&child) {
+            collect_dirs(runtime, &child, output);
+        }
+    }
+}
+
+#[cfg(feature = "eval")]
+/// Collect the files at `path`, walking into it if it's a directory
+pub(crate) fn collect_files<T: Runtime>(runtime: T, path: &str, output: &mut Vec<String>) {
+    match runtime.metadata(path) {
+        Some((true, _, _)) => {
+            for name in runtime.read_dir(path).unwrap_or_default() {
+                collect_files(runtime, &runtime.join_path(path, &[&name]), output);
+            }
+        }
+        Some(_) => output.push(path.to_string()),
+        None => {}
+    }
+}
+
+#[cfg(feature = "eval")]
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+    let (mut p, mut v) = (0, 0);
+    let mut backtrack = None;
+    while v < value.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == value[v]) {
+            p += 1;
+            v += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, v));
+            p += 1;
+        } else if let Some((star_p, star_v)) = backtrack {
+            p = star_p + 1;
+            v = star_v + 1;
+            backtrack = Some((star_p, star_v + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
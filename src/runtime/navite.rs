@@ -1,7 +1,13 @@
 use super::Runtime;
 
 use anyhow::{anyhow, bail, Result};
-use std::{env, fs, path::Path, process, thread};
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    path::Path,
+    process, thread,
+    time::SystemTime,
+};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct NativeRuntime;
@@ -161,6 +167,10 @@ impl Runtime for NativeRuntime {
         Some((is_dir, is_symlink, is_executable))
     }
 
+    fn modified(&self, path: &str) -> Option<SystemTime> {
+        fs::metadata(path).ok()?.modified().ok()
+    }
+
     fn read_dir(&self, path: &str) -> Option<Vec<String>> {
         let dir = fs::read_dir(path).ok()?;
         let mut paths = vec![];
@@ -175,4 +185,12 @@ impl Runtime for NativeRuntime {
         let data = fs::read_to_string(path).ok()?;
         Some(data)
     }
+
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        fs::read(path).ok()
+    }
+
+    fn is_terminal(&self) -> bool {
+        io::stdout().is_terminal()
+    }
 }
@@ -0,0 +1,204 @@
+use crate::{
+    command::Command,
+    param::{ChoiceValue, DefaultValue, RangeValue, ValueType},
+};
+
+use anyhow::{bail, Result};
+use serde_json::{json, Map, Value};
+
+/// JSON Schema describing the output of `--argc-export`
+pub const EXPORT_SCHEMA: &str = include_str!("../docs/export.schema.json");
+
+/// Generate a JSON Schema describing the arguments of the command at `args`,
+/// e.g. `["prog", "build"]` for the subcommand `build`.
+pub fn schema(source: &str, args: &[String]) -> Result<Value> {
+    let Some(name) = args.first() else {
+        bail!("No args, expected the command name")
+    };
+    let root_cmd = Command::new(source, name)?;
+    let mut cmd = &root_cmd;
+    for name in &args[1..] {
+        match cmd.find_subcommand(name) {
+            Some(subcmd) => cmd = subcmd,
+            None => bail!("Unknown command `{}`", name),
+        }
+    }
+    Ok(command_schema(cmd))
+}
+
+fn command_schema(cmd: &Command) -> Value {
+    let mut properties = Map::new();
+    let mut required = vec![];
+    for param in cmd.all_flag_options().into_iter().map(|v| v.export()) {
+        let schema = if param.flag {
+            if param.multiple_occurs {
+                json!({ "type": "integer", "minimum": 0 })
+            } else {
+                json!({ "type": "boolean" })
+            }
+        } else {
+            let schema = value_schema(
+                param.value_type,
+                param.choice.as_ref(),
+                param.default.as_ref(),
+                param.range.as_ref(),
+                param.length.as_ref(),
+                param.pattern.as_deref(),
+            );
+            if param.multiple_values || param.multiple_occurs {
+                array_schema(schema, param.required)
+            } else {
+                schema
+            }
+        };
+        let schema = describe_schema(schema, &param.describe, param.deprecated.is_some());
+        if param.required {
+            required.push(param.id.clone());
+        }
+        properties.insert(param.id, schema);
+    }
+    for param in cmd.positional_params.iter().map(|v| v.export()) {
+        let schema = value_schema(
+            param.value_type,
+            param.choice.as_ref(),
+            param.default.as_ref(),
+            param.range.as_ref(),
+            param.length.as_ref(),
+            param.pattern.as_deref(),
+        );
+        let schema = if param.multiple {
+            array_schema(schema, param.required)
+        } else {
+            schema
+        };
+        let schema = describe_schema(schema, &param.describe, false);
+        if param.required {
+            required.push(param.id.clone());
+        }
+        properties.insert(param.id, schema);
+    }
+    let mut output = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": cmd.cmd_paths().join(" "),
+    });
+    if !cmd.describe.is_empty() {
+        output["description"] = cmd.describe.clone().into();
+    }
+    output["type"] = "object".into();
+    output["properties"] = properties.into();
+    output["required"] = required.into();
+    output["additionalProperties"] = false.into();
+    output
+}
+
+fn value_schema(
+    value_type: Option<ValueType>,
+    choice: Option<&ChoiceValue>,
+    default: Option<&DefaultValue>,
+    range: Option<&RangeValue>,
+    length: Option<&RangeValue>,
+    pattern: Option<&str>,
+) -> Value {
+    // A range is checked numerically, so the value is a number whatever its declared type
+    let value_type = match value_type {
+        Some(ValueType::Int) => value_type,
+        _ if range.is_some() => Some(ValueType::Float),
+        _ => value_type,
+    };
+    let mut output = Map::new();
+    match (choice, value_type) {
+        (Some(ChoiceValue::Values(values)), _) => {
+            output.insert("type".into(), "string".into());
+            output.insert("enum".into(), values.clone().into());
+        }
+        (_, Some(ValueType::Int)) => {
+            output.insert("type".into(), "integer".into());
+        }
+        (_, Some(ValueType::Float)) => {
+            output.insert("type".into(), "number".into());
+        }
+        (_, Some(ValueType::Bool)) => {
+            output.insert("type".into(), "boolean".into());
+        }
+        (_, Some(ValueType::Url)) => {
+            output.insert("type".into(), "string".into());
+            output.insert("format".into(), "uri".into());
+        }
+        _ => {
+            output.insert("type".into(), "string".into());
+        }
+    }
+    if let Some(DefaultValue::Value(value)) = default {
+        let value = match choice {
+            Some(ChoiceValue::Values(_)) => value.as_str().into(),
+            _ => typed_value(value, value_type),
+        };
+        output.insert("default".into(), value);
+    }
+    if let Some(range) = range {
+        if let Some(min) = range.min() {
+            output.insert("minimum".into(), number_value(min));
+        }
+        if let Some(max) = range.max() {
+            let key = if range.inclusive() {
+                "maximum"
+            } else {
+                "exclusiveMaximum"
+            };
+            output.insert(key.into(), number_value(max));
+        }
+    }
+    if let Some(length) = length {
+        if let Some(min) = length.min() {
+            output.insert("minLength".into(), (min.max(0.0) as u64).into());
+        }
+        if let Some(max) = length.max() {
+            let max = if length.inclusive() { max } else { max - 1.0 };
+            output.insert("maxLength".into(), (max.max(0.0) as u64).into());
+        }
+    }
+    if let Some(pattern) = pattern {
+        output.insert("pattern".into(), pattern.into());
+    }
+    output.into()
+}
+
+fn array_schema(items: Value, required: bool) -> Value {
+    let mut output = json!({ "type": "array", "items": items });
+    if required {
+        output["minItems"] = 1.into();
+    }
+    output
+}
+
+fn describe_schema(mut schema: Value, describe: &str, deprecated: bool) -> Value {
+    if !describe.is_empty() {
+        schema["description"] = describe.into();
+    }
+    if deprecated {
+        schema["deprecated"] = true.into();
+    }
+    schema
+}
+
+fn typed_value(value: &str, value_type: Option<ValueType>) -> Value {
+    let typed = match value_type {
+        Some(ValueType::Int) => value.parse::<i64>().ok().map(Value::from),
+        Some(ValueType::Float) => value.parse::<f64>().ok().map(number_value),
+        Some(ValueType::Bool) => match value {
+            "true" | "1" => Some(true.into()),
+            "false" | "0" => Some(false.into()),
+            _ => None,
+        },
+        _ => None,
+    };
+    typed.unwrap_or_else(|| value.into())
+}
+
+fn number_value(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        (value as i64).into()
+    } else {
+        value.into()
+    }
+}
@@ -1,3 +1,5 @@
+use crate::locale::{format_message, Messages};
+
 use convert_case::{Boundary, Converter, Pattern};
 
 pub const VARIABLE_PREFIX: &str = "argc_";
@@ -15,9 +17,28 @@ pub(crate) const META_SYMBOL: &str = "symbol";
 pub(crate) const META_COMBINE_SHORTS: &str = "combine-shorts";
 pub(crate) const META_MAN_SECTION: &str = "man-section";
 pub(crate) const META_REQUIRE_TOOLS: &str = "require-tools";
+pub(crate) const META_DEPS: &str = "deps";
+pub(crate) const META_PARALLEL_DEPS: &str = "parallel-deps";
+pub(crate) const META_SOURCES: &str = "sources";
+pub(crate) const META_TARGETS: &str = "targets";
+pub(crate) const META_CACHE: &str = "cache";
+pub(crate) const META_CONFLICTS: &str = "conflicts";
+pub(crate) const META_REQUIRES: &str = "requires";
+pub(crate) const META_ONE_OF_REQUIRED: &str = "one-of-required";
+pub(crate) const META_LOCALE: &str = "locale";
 
 pub(crate) const MAX_ARGS: usize = 32767;
 
+/// ANSI styles of the help, the build mode maps them to the variables set by `_argc_help_styles`
+#[cfg(any(feature = "build", feature = "eval"))]
+pub(crate) const STYLE_HEADING: &str = "\x1b[1;32m";
+#[cfg(any(feature = "build", feature = "eval"))]
+pub(crate) const STYLE_LITERAL: &str = "\x1b[1;36m";
+#[cfg(any(feature = "build", feature = "eval"))]
+pub(crate) const STYLE_PLACEHOLDER: &str = "\x1b[36m";
+#[cfg(any(feature = "build", feature = "eval"))]
+pub(crate) const STYLE_RESET: &str = "\x1b[0m";
+
 #[cfg(any(feature = "build", feature = "eval-bash"))]
 pub const ARGC_REQUIRE_TOOLS: &str = r#"_argc_require_tools() {
     local tool missing_tools=()
@@ -32,6 +53,41 @@ pub const ARGC_REQUIRE_TOOLS: &str = r#"_argc_require_tools() {
     fi
 }"#;
 
+#[cfg(any(feature = "build", feature = "eval-bash"))]
+pub const ARGC_UP_TO_DATE: &str = r#"_argc_up_to_date() {
+    local item file target targets=() files=() matches=() IFS=
+    while [[ $# -gt 0 ]] && [[ "$1" != "--" ]]; do
+        targets+=("$1")
+        shift
+    done
+    shift
+    local shopts="$(shopt -p globstar nullglob)"
+    shopt -s globstar nullglob
+    for item in "$@"; do
+        matches=($item)
+        for file in "${matches[@]}"; do
+            if [[ -d "$file" ]]; then
+                while IFS= read -r file; do
+                    files+=("$file")
+                done < <(find "$file" -type f)
+            else
+                files+=("$file")
+            fi
+        done
+    done
+    eval "$shopts"
+    for target in "${targets[@]}"; do
+        if [[ ! -e "$target" ]]; then
+            return 1
+        fi
+        for file in "${files[@]}"; do
+            if [[ ! "$target" -nt "$file" ]]; then
+                return 1
+            fi
+        done
+    done
+}"#;
+
 #[cfg(any(feature = "build", feature = "eval-bash"))]
 pub const ARGC_LOAD_DOTENV: &str = r#"_argc_load_dotenv() {
     local env_file="$1" env_vars=""
@@ -58,6 +114,17 @@ pub fn to_cobol_case(value: &str) -> String {
         .convert(value)
 }
 
+pub(crate) fn render_deprecated(messages: &Messages, name: &str, message: &str) -> String {
+    if message.is_empty() {
+        format_message(messages.deprecated_warning, &[("name", name)])
+    } else {
+        format_message(
+            messages.deprecated_warning_with_message,
+            &[("name", name), ("message", message)],
+        )
+    }
+}
+
 pub fn escape_shell_words(value: &str) -> String {
     shell_words::quote(value).to_string()
 }
@@ -112,6 +179,65 @@ pub fn is_true_value(value: &str) -> bool {
     matches!(value, "true" | "1")
 }
 
+/// Find the candidate closest to a mistyped value, within an edit distance of a third of their length.
+///
+/// Must stay in sync with `_argc_did_you_mean` of the build mode.
+pub(crate) fn did_you_mean<'a>(value: &str, candidates: &'a [String]) -> Option<&'a str> {
+    let value_len = value.chars().count();
+    let mut output: Option<(usize, &str)> = None;
+    for candidate in candidates {
+        let distance = edit_distance(value, candidate);
+        if distance * 3 > value_len.max(candidate.chars().count()) {
+            continue;
+        }
+        if output.map(|(v, _)| distance < v).unwrap_or(true) {
+            output = Some((distance, candidate));
+        }
+    }
+    output.map(|(_, v)| v)
+}
+
+pub(crate) fn render_did_you_mean(
+    messages: &Messages,
+    value: &str,
+    candidates: &[String],
+) -> String {
+    match did_you_mean(value, candidates) {
+        Some(candidate) => format!(
+            "\n  {}",
+            format_message(messages.did_you_mean, &[("value", candidate)])
+        ),
+        None => String::new(),
+    }
+}
+
+/// Optimal string alignment distance, an adjacent transposition counts as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let width = b.len() + 1;
+    let mut d = vec![0; (a.len() + 1) * width];
+    for i in 0..=a.len() {
+        d[i * width] = i;
+    }
+    for (j, v) in d.iter_mut().enumerate().take(width) {
+        *v = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut min = (d[(i - 1) * width + j] + 1)
+                .min(d[i * width + j - 1] + 1)
+                .min(d[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                min = min.min(d[(i - 2) * width + j - 2] + 1);
+            }
+            d[i * width + j] = min;
+        }
+    }
+    d[a.len() * width + b.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +248,17 @@ mod tests {
         assert_eq!("FOO-BAR".to_string(), to_cobol_case("foo-bar"));
         assert_eq!("FOO1".to_string(), to_cobol_case("foo1"));
     }
+
+    #[test]
+    fn test_did_you_mean() {
+        let candidates: Vec<String> = ["build", "test", "--verbose", "-v"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(did_you_mean("biuld", &candidates), Some("build"));
+        assert_eq!(did_you_mean("tets", &candidates), Some("test"));
+        assert_eq!(did_you_mean("--verbsoe", &candidates), Some("--verbose"));
+        assert_eq!(did_you_mean("-x", &candidates), None);
+        assert_eq!(did_you_mean("deploy", &candidates), None);
+    }
 }
@@ -1,15 +1,21 @@
 use assert_cmd::prelude::*;
-use assert_fs::fixture::PathChild;
-use std::{process::Command, time::Instant};
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use std::{
+    io::{BufRead, BufReader},
+    process::{Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant, UNIX_EPOCH},
+};
 
 use crate::fixtures::{
-    get_path_env_var, locate_script, tmpdir, tmpdir_argcfiles, tmpdir_path, SCRIPT_PATHS,
+    argc_command, get_path_env_var, locate_script, tmpdir, tmpdir_argcfiles, tmpdir_path,
+    SCRIPT_PATHS, TEST_ENVS,
 };
 
 #[test]
 fn version() {
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .arg("--argc-version")
         .assert()
         .stdout(predicates::str::contains(format!(
@@ -21,8 +27,7 @@ fn version() {
 
 #[test]
 fn help() {
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .arg("--argc-help")
         .assert()
         .stdout(predicates::str::contains(env!("CARGO_PKG_DESCRIPTION")))
@@ -33,16 +38,14 @@ fn help() {
 fn create() {
     let tmpdir = tmpdir();
     let path_env_var = get_path_env_var();
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .current_dir(tmpdir.path())
         .env("PATH", path_env_var.clone())
         .arg("--argc-create")
         .assert()
         .success();
     assert!(tmpdir.path().join("Argcfile.sh").exists());
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .current_dir(tmpdir.path())
         .env("PATH", path_env_var)
         .assert()
@@ -53,16 +56,14 @@ fn create() {
 fn create_with_tasks() {
     let tmpdir = tmpdir();
     let path_env_var = get_path_env_var();
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .current_dir(tmpdir.path())
         .env("PATH", path_env_var.clone())
         .arg("--argc-create")
         .args(["foo", "bar"])
         .assert()
         .success();
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .current_dir(tmpdir.path())
         .env("PATH", path_env_var)
         .arg("bar")
@@ -75,8 +76,7 @@ fn create_with_tasks() {
 fn run() {
     let path_env_var = get_path_env_var();
     let path = locate_script("examples/demo.sh");
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .arg("--argc-run")
         .arg(path)
         .env("PATH", path_env_var)
@@ -88,8 +88,7 @@ fn run() {
 #[test]
 fn build_stdout() {
     let path = locate_script("examples/demo.sh");
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .arg("--argc-build")
         .arg(path)
         .assert()
@@ -97,21 +96,48 @@ fn build_stdout() {
         .success();
 }
 
+#[test]
+fn build_styled_help() {
+    let path = locate_script("examples/demo.sh");
+    argc_command()
+        .arg("--argc-build")
+        .arg(path)
+        .assert()
+        .stdout(predicates::str::contains(
+            "${_argc_style_heading}USAGE:${_argc_style_reset} ${_argc_style_literal}demo${_argc_style_reset}",
+        ))
+        .stdout(predicates::str::contains("_argc_help_styles() {"))
+        .success();
+}
+
+#[test]
+fn eval_locale_from_env() {
+    let path = locate_script("examples/demo.sh");
+    argc_command()
+        .env_remove("LC_ALL")
+        .env("LANG", "zh_CN.UTF-8")
+        .arg("--argc-eval")
+        .arg(path)
+        .args(["demo", "-h"])
+        .assert()
+        .stdout(predicates::str::contains("用法: demo"))
+        .stdout(predicates::str::contains("命令:"))
+        .success();
+}
+
 #[test]
 fn run_build() {
     let path = locate_script("examples/strict.sh");
     let tmpdir = tmpdir();
     let outpath = tmpdir.join("strict.sh");
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .arg("--argc-build")
         .arg(&path)
         .arg(&outpath)
         .assert()
         .success();
 
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .arg("--argc-run")
         .arg(&outpath)
         .args([
@@ -133,8 +159,7 @@ fn mangen() {
     let path = locate_script("examples/demo.sh");
     let tmpdir = tmpdir();
     let outdir = tmpdir.to_path_buf();
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .arg("--argc-mangen")
         .arg(&path)
         .arg(&outdir)
@@ -143,12 +168,38 @@ fn mangen() {
     let manpath = outdir.join("demo.1");
     let script = std::fs::read_to_string(manpath).unwrap();
     assert!(script.contains(".TH DEMO 1"));
+
+    let path = locate_script("examples/envs.sh");
+    argc_command()
+        .arg("--argc-mangen")
+        .arg(&path)
+        .arg(&outdir)
+        .assert()
+        .success();
+    let script = std::fs::read_to_string(outdir.join("envs.1")).unwrap();
+    assert!(script.contains(".SH \"ENVIRONMENT VARIABLES\"\n"));
+}
+
+#[test]
+fn markdown() {
+    let path = locate_script("examples/demo.sh");
+    let tmpdir = tmpdir();
+    let outdir = tmpdir.to_path_buf();
+    argc_command()
+        .arg("--argc-markdown")
+        .arg(&path)
+        .arg(&outdir)
+        .assert()
+        .success();
+    let page = std::fs::read_to_string(outdir.join("demo.md")).unwrap();
+    assert!(page.starts_with("# demo\n"));
+    assert!(page.contains("- [`upload`](demo-upload.md): Upload a file [aliases: u]"));
+    assert!(outdir.join("demo-upload.md").exists());
 }
 
 #[test]
 fn completions() {
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .args(["--argc-completions", "bash", "mycmd1", "mycmd2"])
         .assert()
         .stdout(predicates::str::contains(r#"argc mycmd1 mycmd2"#))
@@ -159,8 +210,7 @@ fn completions() {
 fn compgen_args() {
     let path = locate_script("examples/args.sh");
     let path_env_var = get_path_env_var();
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .arg("--argc-compgen")
         .arg("fish")
         .arg(path)
@@ -175,8 +225,7 @@ fn compgen_args() {
 fn compgen_options() {
     let path = locate_script("examples/options.sh");
     let path_env_var = get_path_env_var();
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .arg("--argc-compgen")
         .arg("fish")
         .arg(path)
@@ -189,8 +238,7 @@ fn compgen_options() {
 
 #[test]
 fn compgen_argc() {
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .args(["--argc-compgen", "fish", "", "argc", "--argc-compgen", ""])
         .assert()
         .stdout(predicates::str::contains("zsh"))
@@ -199,8 +247,7 @@ fn compgen_argc() {
 
 #[test]
 fn compgen_kind() {
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .args([
             "--argc-compgen",
             "fish",
@@ -216,8 +263,7 @@ fn compgen_kind() {
 #[test]
 fn export() {
     let path = locate_script("examples/options.sh");
-    let output = Command::cargo_bin("argc")
-        .unwrap()
+    let output = argc_command()
         .arg("--argc-export")
         .arg(path)
         .output()
@@ -226,14 +272,640 @@ fn export() {
     insta::assert_snapshot!(stdout);
 }
 
+#[test]
+fn export_schema() {
+    let output = argc_command().arg("--argc-schema").output().unwrap();
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["$id"], "urn:argc:export:v1");
+    let defs = &schema["$defs"];
+
+    fn check_keys(defs: &serde_json::Value, value: &serde_json::Value, def: &str) {
+        for key in value.as_object().unwrap().keys() {
+            assert!(
+                defs[def]["properties"].get(key).is_some(),
+                "`{key}` is missing in $defs.{def}"
+            );
+        }
+        for (field, def) in [
+            ("flag_options", "flagOption"),
+            ("positionals", "positional"),
+            ("envs", "env"),
+            ("subcommands", "command"),
+        ] {
+            for item in value[field].as_array().into_iter().flatten() {
+                check_keys(defs, item, def);
+            }
+        }
+    }
+    let mut paths: Vec<_> = std::fs::read_dir(locate_script("examples"))
+        .unwrap()
+        .map(|v| v.unwrap().path())
+        .filter(|v| v.extension().is_some_and(|v| v == "sh"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+    for path in paths {
+        let output = argc_command()
+            .arg("--argc-export")
+            .arg(&path)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", path.display());
+        let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        check_keys(defs, &value, "command");
+    }
+}
+
+#[test]
+fn schema_subcommand() {
+    let script = r###"
+# @meta inherit-flag-options
+# @option --oa
+# @cmd
+# @flag --fb
+sub() { :; }
+"###;
+    let (script_path, _, script_file) = crate::fixtures::create_argc_script(script, "demo.sh");
+    let output = argc_command()
+        .arg("--argc-schema")
+        .arg(&script_path)
+        .arg("sub")
+        .output()
+        .unwrap();
+    script_file.close().unwrap();
+    assert!(output.status.success());
+    let stdout = std::str::from_utf8(&output.stdout).unwrap();
+    insta::assert_snapshot!(stdout);
+}
+
+#[test]
+fn schema() {
+    let script = r###"
+# @describe A demo cli
+# @flag --fa
+# @flag -v* verbose
+# @option --oa!
+# @option --ob*,
+# @option --oc[=a|b|c]
+# @option --od=3 {int,1..=9}
+# @option --oe {len=..8}
+# @option --og {0..1}
+# @option --of[/^v[0-9]+$/]
+# @deprecated
+# @arg name! The name
+# @arg files*
+"###;
+    let (script_path, _, script_file) = crate::fixtures::create_argc_script(script, "demo.sh");
+    let output = argc_command()
+        .arg("--argc-schema")
+        .arg(&script_path)
+        .output()
+        .unwrap();
+    script_file.close().unwrap();
+    assert!(output.status.success());
+    let stdout = std::str::from_utf8(&output.stdout).unwrap();
+    insta::assert_snapshot!(stdout);
+    assert!(argc::schema(script, &[]).is_err());
+}
+
+#[test]
+fn import() {
+    let tmpdir = tmpdir();
+    let export = |path: &str| {
+        let output = argc_command()
+            .arg("--argc-export")
+            .arg(path)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let import = |json: &str, name: &str| {
+        let json_path = tmpdir.child(format!("{name}.json"));
+        std::fs::write(&json_path, json).unwrap();
+        let output = argc_command()
+            .arg("--argc-import")
+            .arg(json_path.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let script = String::from_utf8(output.stdout).unwrap();
+        let script_path = tmpdir.child(format!("{name}.sh"));
+        std::fs::write(&script_path, &script).unwrap();
+        (script, script_path.to_string_lossy().to_string())
+    };
+    for name in [
+        "args",
+        "bind-envs",
+        "hooks",
+        "nested-commands",
+        "options",
+        "symbol",
+    ] {
+        let json = export(&locate_script(&format!("examples/{name}.sh")));
+        let (_, script_path) = import(&json, name);
+        assert_eq!(export(&script_path), json, "{name}");
+    }
+
+    let script = r###"
+# @describe A demo cli
+# @meta conflicts fa,fb
+# @flag --fa
+# @deprecated Use --fb instead
+# @hidden
+# @heading Advanced
+# @flag --fb
+# @option --oa[/^v\d+$/] {int,1..=9}
+# @example prog --fb
+# Run with fb
+
+# @cmd Clean up
+# @hidden
+# @alias c
+# @deprecated
+# @arg files*
+clean_up() { :; }
+"###;
+    let (script_path, _, script_file) = crate::fixtures::create_argc_script(script, "demo.sh");
+    let json = export(&script_path);
+    script_file.close().unwrap();
+    let (script, script_path) = import(&json, "demo");
+    assert_eq!(export(&script_path), json);
+    insta::assert_snapshot!(script);
+}
+
+#[test]
+fn up_to_date() {
+    let source = r#"#!/usr/bin/env bash
+set -e
+
+# @cmd
+# @meta sources src/**/*.c
+# @meta targets out/app
+build() {
+    mkdir -p out
+    touch out/app
+    echo build
+}
+
+eval "$(argc --argc-eval "$0" "$@")"
+"#;
+    let build_source = argc::build(source, "prog", None).unwrap();
+    let path_env_var = get_path_env_var();
+    for (name, content) in [("Argcfile.sh", source), ("prog.sh", build_source.as_str())] {
+        let tmpdir = tmpdir();
+        tmpdir.child(name).write_str(content).unwrap();
+        tmpdir.child("src/lib/main.c").write_str("").unwrap();
+        let run = |args: &[&str]| {
+            let output = Command::new("bash")
+                .current_dir(tmpdir.path())
+                .envs(TEST_ENVS)
+                .env("PATH", path_env_var.clone())
+                .arg(name)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            )
+        };
+        assert_eq!(run(&["build"]), "build\n");
+        assert_eq!(run(&["build"]), "`build` is up to date\n");
+        assert_eq!(run(&["build", "--force"]), "build\n");
+        std::fs::File::options()
+            .write(true)
+            .open(tmpdir.child("out/app").path())
+            .unwrap()
+            .set_modified(UNIX_EPOCH + Duration::from_secs(1000))
+            .unwrap();
+        assert_eq!(run(&["build"]), "build\n");
+        assert_eq!(run(&["build"]), "`build` is up to date\n");
+    }
+}
+
+#[test]
+fn up_to_date_after_deps() {
+    let source = r#"#!/usr/bin/env bash
+set -e
+
+# @cmd
+# @meta deps gen
+# @meta sources src dir/**/*.c
+# @meta targets out/app
+build() {
+    mkdir -p out
+    touch out/app
+    echo build
+}
+
+# @cmd
+gen() {
+    mkdir -p "src dir"
+    if [[ -f regen ]]; then
+        touch "src dir/main.c"
+    fi
+    echo gen
+}
+
+eval "$(argc --argc-eval "$0" "$@")"
+"#;
+    let build_source = argc::build(source, "prog", None).unwrap();
+    let path_env_var = get_path_env_var();
+    for (name, content) in [("Argcfile.sh", source), ("prog.sh", build_source.as_str())] {
+        let tmpdir = tmpdir();
+        tmpdir.child(name).write_str(content).unwrap();
+        let run = |args: &[&str]| {
+            let output = Command::new("bash")
+                .current_dir(tmpdir.path())
+                .envs(TEST_ENVS)
+                .env("PATH", path_env_var.clone())
+                .arg(name)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            )
+        };
+        assert_eq!(run(&["build"]), "gen\nbuild\n");
+        assert_eq!(run(&["build"]), "gen\n`build` is up to date\n");
+        tmpdir.child("regen").write_str("").unwrap();
+        assert_eq!(run(&["build"]), "gen\nbuild\n");
+    }
+}
+
+#[test]
+fn failing_deps() {
+    let source = r#"#!/usr/bin/env bash
+
+# @cmd
+a() { return 3; }
+
+# @cmd
+# @meta deps a
+b() { echo b; }
+
+# @cmd
+# @meta deps a,b
+# @meta parallel-deps
+c() { echo c; }
+
+eval "$(argc --argc-eval "$0" "$@")"
+"#;
+    let build_source = argc::build(source, "prog", None).unwrap();
+    let path_env_var = get_path_env_var();
+    for (name, content) in [("Argcfile.sh", source), ("prog.sh", build_source.as_str())] {
+        let tmpdir = tmpdir();
+        tmpdir.child(name).write_str(content).unwrap();
+        let run = |args: &[&str]| {
+            let output = Command::new("bash")
+                .current_dir(tmpdir.path())
+                .envs(TEST_ENVS)
+                .env("PATH", path_env_var.clone())
+                .arg(name)
+                .args(args)
+                .output()
+                .unwrap();
+            (
+                output.status.code(),
+                String::from_utf8_lossy(&output.stdout).to_string(),
+            )
+        };
+        assert_eq!(run(&["b"]), (Some(3), String::new()));
+        let (code, stdout) = run(&["c"]);
+        assert_ne!(code, Some(0));
+        assert!(!stdout.contains('c'));
+    }
+}
+
+#[test]
+fn eval_recipe_once() {
+    let source = r#"#!/usr/bin/env bash
+
+# @cmd
+# @option --mode[`_choice_mode`]
+build() {
+    echo build $argc_mode
+}
+
+_choice_mode() {
+    echo choice >> calls
+    echo dev
+}
+
+eval "$(argc --argc-eval "$0" "$@")"
+"#;
+    let tmpdir = tmpdir();
+    tmpdir.child("Argcfile.sh").write_str(source).unwrap();
+    let output = argc_command()
+        .current_dir(tmpdir.path())
+        .env("PATH", get_path_env_var())
+        .args(["build", "--mode", "dev"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "build dev\n");
+    let calls = std::fs::read_to_string(tmpdir.child("calls").path()).unwrap();
+    assert_eq!(calls, "choice\n");
+}
+
+#[test]
+fn cache() {
+    let source = r#"#!/usr/bin/env bash
+set -e
+
+# @cmd
+# @meta cache
+# @meta sources src
+# @option --mode[=dev|prod] $$
+# @arg name
+build() {
+    echo build $1
+}
+
+eval "$(argc --argc-eval "$0" "$@")"
+"#;
+    let tmpdir = tmpdir();
+    let path_env_var = get_path_env_var();
+    tmpdir.child("Argcfile.sh").write_str(source).unwrap();
+    tmpdir.child("src/main.c").write_str("v1").unwrap();
+    let run_with_envs = |args: &[&str], envs: &[(&str, &str)]| {
+        let output = argc_command()
+            .current_dir(tmpdir.path())
+            .env("PATH", path_env_var.clone())
+            .envs(envs.iter().copied())
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )
+    };
+    let run = |args: &[&str]| run_with_envs(args, &[]);
+    assert_eq!(run(&["build", "a"]), "build a\n");
+    assert_eq!(run(&["build", "a"]), "`build` is cached\n");
+    assert_eq!(run(&["build", "b"]), "build b\n");
+    tmpdir.child("src/main.c").write_str("v2").unwrap();
+    assert_eq!(run(&["build", "a"]), "build a\n");
+    assert_eq!(run(&["build", "a"]), "`build` is cached\n");
+    tmpdir
+        .child("Argcfile.sh")
+        .write_str(&source.replace("echo build $1", "echo rebuild $1"))
+        .unwrap();
+    assert_eq!(run(&["build", "a"]), "rebuild a\n");
+    assert_eq!(run(&["build", "a", "--mode", "dev"]), "`build` is cached\n");
+    assert_eq!(
+        run_with_envs(&["build", "a"], &[("MODE", "prod")]),
+        "rebuild a\n"
+    );
+    assert!(tmpdir.child(argc::CACHE_DIR).path().is_dir());
+
+    let export = run(&["--argc-export", "Argcfile.sh"]);
+    let value: serde_json::Value = serde_json::from_str(&export).unwrap();
+    assert_eq!(
+        value["subcommands"][0]["sources"],
+        serde_json::json!(["src"])
+    );
+    assert_eq!(value["subcommands"][0]["cache"], true);
+}
+
+#[test]
+fn watch() {
+    let tmpdir = tmpdir();
+    let path_env_var = get_path_env_var();
+    tmpdir
+        .child("Argcfile.sh")
+        .write_str(
+            r#"
+# @cmd
+test() { echo test $1; }
+eval "$(argc --argc-eval "$0" "$@")"
+"#,
+        )
+        .unwrap();
+    tmpdir.child("src/main.c").write_str("v1").unwrap();
+    argc_command()
+        .current_dir(tmpdir.path())
+        .args(["--argc-watch", "--", "test", "a"])
+        .assert()
+        .failure();
+    let mut child = argc_command()
+        .current_dir(tmpdir.path())
+        .env("PATH", path_env_var)
+        .args(["--argc-watch", "src", "--", "test", "a"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stderr = child.stderr.take().unwrap();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            let _ = tx.send(line);
+        }
+    });
+    let wait_for = |expected: &str| {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(line) if line == expected => break,
+                Ok(_) => {}
+                Err(_) => panic!("timed out waiting for `{expected}`"),
+            }
+        }
+    };
+    wait_for("==== [1] argc test a ====");
+    wait_for("==== exit 0, waiting for changes ====");
+    tmpdir.child("src/main.c").write_str("v2").unwrap();
+    wait_for("==== [2] argc test a ====");
+    wait_for("==== exit 0, waiting for changes ====");
+    child.kill().unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "test a\ntest a\n");
+}
+
+#[test]
+fn trace() {
+    let source = r#"#!/usr/bin/env bash
+set -e
+
+# @cmd
+# @option --mode=debug
+# @arg files*
+build() {
+    argc test a
+}
+
+# @cmd
+# @arg name
+test() {
+    echo test $1
+    exit 2
+}
+
+# @cmd
+stop() {
+    kill -TERM $$
+}
+
+eval "$(argc --argc-eval "$0" "$@")"
+"#;
+    let tmpdir = tmpdir();
+    let path_env_var = get_path_env_var();
+    tmpdir.child("Argcfile.sh").write_str(source).unwrap();
+    let trace_file = tmpdir.child("trace.jsonl");
+    let output = argc_command()
+        .current_dir(tmpdir.path())
+        .env("PATH", path_env_var.clone())
+        .env("ARGC_TRACE_FILE", trace_file.path())
+        .args(["build", "x", "y"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "test a\n");
+    let records: Vec<serde_json::Value> = std::fs::read_to_string(trace_file.path())
+        .unwrap()
+        .lines()
+        .map(|v| serde_json::from_str(v).unwrap())
+        .collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["recipe"], "test");
+    assert_eq!(records[0]["depth"], 1);
+    assert_eq!(records[0]["exit"], 2);
+    assert_eq!(records[0]["values"], serde_json::json!({ "name": "a" }));
+    assert_eq!(records[1]["recipe"], "build");
+    assert_eq!(records[1]["args"], serde_json::json!(["build", "x", "y"]));
+    assert_eq!(records[1]["depth"], 0);
+    assert_eq!(records[1]["exit"], 2);
+    assert_eq!(
+        records[1]["values"],
+        serde_json::json!({ "mode": "debug", "files": ["x", "y"] })
+    );
+    assert!(records[1]["duration"].as_f64().unwrap() >= records[0]["duration"].as_f64().unwrap());
+
+    let output = argc_command()
+        .current_dir(tmpdir.path())
+        .env("PATH", path_env_var.clone())
+        .env("ARGC_TRACE", "1")
+        .args(["test", "b"])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with(
+        r#"{"recipe":"test","args":["test","b"],"values":{"name":"b"},"depth":0,"exit":2,"#
+    ));
+
+    #[cfg(not(windows))]
+    {
+        let output = argc_command()
+            .current_dir(tmpdir.path())
+            .env("PATH", path_env_var)
+            .env("ARGC_TRACE", "1")
+            .arg("stop")
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(143));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(r#""exit":143,"#));
+    }
+}
+
+#[test]
+fn eval_json() {
+    let source = r#"#!/usr/bin/env bash
+# @cmd
+# @option --mode[debug|release]
+# @option -D* <KEY=VALUE>
+# @arg files*
+build() { :; }
+
+eval "$(argc --argc-eval "$0" "$@")"
+"#;
+    let tmpdir = tmpdir();
+    tmpdir.child("prog.sh").write_str(source).unwrap();
+    let script_file = tmpdir.child("prog.sh").path().display().to_string();
+    let run = |args: &[&str]| {
+        let output = argc_command()
+            .arg("--argc-eval-json")
+            .arg(&script_file)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    let text = format!(
+        "{}{}",
+        run(&["build", "--mode", "debug", "-D", "a=1", "x"]),
+        run(&["build", "--mode", "dev"])
+    );
+    insta::assert_snapshot!(text);
+}
+
+#[test]
+fn dry_run() {
+    let source = r#"#!/usr/bin/env bash
+# @meta dotenv
+# @meta require-tools git,bash
+# @env FOO=bar
+
+# @cmd
+# @meta deps test,lint
+# @meta parallel-deps
+# @option --mode=debug
+# @option -D* defines
+# @flag -v
+# @arg files*
+build() { :; }
+
+# @cmd
+test() { :; }
+
+# @cmd
+lint() { :; }
+
+_argc_before() { :; }
+
+eval "$(argc --argc-eval "$0" "$@")"
+"#;
+    let tmpdir = tmpdir();
+    tmpdir.child("prog.sh").write_str(source).unwrap();
+    let script_file = tmpdir.child("prog.sh").path().display().to_string();
+    let run = |args: &[&str]| {
+        let output = argc_command()
+            .arg("--argc-dry-run")
+            .arg(&script_file)
+            .args(args)
+            .output()
+            .unwrap();
+        (
+            output.status.code(),
+            String::from_utf8_lossy(&output.stdout).to_string(),
+        )
+    };
+    let (code, build_output) = run(&["build", "-v", "-D", "a=1", "-D", "b c", "x", "y z"]);
+    assert_eq!(code, Some(0));
+    let (code, error_output) = run(&["build", "--mode"]);
+    assert_eq!(code, Some(1));
+    let text = format!("{build_output}\n{error_output}");
+    insta::assert_snapshot!(text);
+}
+
 #[test]
 fn parallel() {
     let tmpdir = tmpdir_argcfiles();
     let path_env_var = get_path_env_var();
     let args = ["task2", "--oa", "3"];
     let start_time = Instant::now();
-    let output = Command::cargo_bin("argc")
-        .unwrap()
+    let output = argc_command()
         .current_dir(tmpdir_path(&tmpdir, "dir1"))
         .env("PATH", path_env_var)
         .args(args)
@@ -260,11 +932,172 @@ STDERR:
     insta::assert_snapshot!(text);
 }
 
+#[test]
+fn parallel_options() {
+    let source = r#"#!/usr/bin/env bash
+set -e
+
+# @cmd
+ok() {
+    echo ok
+}
+
+# @cmd
+fail() {
+    sleep 0.2
+    echo fail >&2
+    exit 3
+}
+
+# @cmd
+slow() {
+    sleep 3 &
+    echo $! > slow.pid
+    wait
+    echo slow
+}
+
+# @cmd
+run() {
+    argc --argc-parallel "$0" fail ::: slow ::: ok
+}
+
+eval "$(argc --argc-eval "$0" "$@")"
+"#;
+    let tmpdir = tmpdir();
+    let path_env_var = get_path_env_var();
+    tmpdir.child("Argcfile.sh").write_str(source).unwrap();
+    let run = |envs: &[(&str, &str)]| {
+        let start_time = Instant::now();
+        let output = argc_command()
+            .current_dir(tmpdir.path())
+            .env("PATH", path_env_var.clone())
+            .env("ARGC_PARALLEL_JOBS", "3")
+            .envs(envs.iter().copied())
+            .arg("run")
+            .output()
+            .unwrap();
+        (
+            output.status.code(),
+            String::from_utf8_lossy(&output.stdout).to_string(),
+            String::from_utf8_lossy(&output.stderr).to_string(),
+            start_time.elapsed(),
+        )
+    };
+
+    let (code, stdout, stderr, _) = run(&[]);
+    assert_eq!(code, Some(3));
+    assert_eq!(stdout, "slow\nok\n");
+    assert_eq!(stderr, "fail\n");
+
+    std::fs::remove_file(tmpdir.child("slow.pid").path()).unwrap();
+    let (code, stdout, _, elapsed) = run(&[("ARGC_PARALLEL_FAIL_FAST", "1")]);
+    assert_eq!(code, Some(3));
+    assert!(!stdout.contains("slow"));
+    assert!(elapsed < Duration::from_millis(2500));
+    // The processes spawned by the cancelled job are stopped too
+    #[cfg(target_os = "linux")]
+    {
+        let pid = std::fs::read_to_string(tmpdir.child("slow.pid").path()).unwrap();
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid.trim()));
+        assert!(stat.map(|v| v.contains(") Z ")).unwrap_or(true));
+    }
+
+    let (code, stdout, stderr, _) = run(&[("ARGC_PARALLEL_OUTPUT", "stream")]);
+    assert_eq!(code, Some(3));
+    assert_eq!(stdout, "[ok] ok\n[slow] slow\n");
+    assert_eq!(stderr, "[fail] fail\n");
+
+    let report_file = tmpdir.child("report.json");
+    let (code, _, stderr, _) = run(&[
+        ("ARGC_PARALLEL_FAIL_FAST", "1"),
+        ("ARGC_PARALLEL_SUMMARY", "1"),
+        (
+            "ARGC_PARALLEL_REPORT",
+            &report_file.path().display().to_string(),
+        ),
+    ]);
+    assert_eq!(code, Some(3));
+    let summary: Vec<&str> = stderr
+        .lines()
+        .skip_while(|v| !v.starts_with("==== parallel summary"))
+        .skip(1)
+        .map(|v| {
+            let parts: Vec<&str> = v.split_whitespace().collect();
+            parts[parts.len() - 1]
+        })
+        .collect();
+    assert_eq!(summary, ["fail", "slow", "ok"]);
+    assert!(stderr.contains("exit 3 "));
+    assert!(stderr.contains("cancelled "));
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(report_file.path()).unwrap()).unwrap();
+    assert_eq!(report["exit"], 3);
+    let jobs: Vec<(&str, &str)> = report["jobs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| {
+            (
+                v["args"][0].as_str().unwrap(),
+                v["status"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        jobs,
+        [("fail", "failed"), ("slow", "cancelled"), ("ok", "ok")]
+    );
+    assert!(report["jobs"][0]["duration"].as_f64().unwrap() > 0.1);
+}
+
+#[cfg(not(windows))]
+#[test]
+fn parallel_signaled() {
+    let source = r#"#!/usr/bin/env bash
+
+# @cmd
+ok() {
+    echo ok
+}
+
+# @cmd
+term() {
+    kill -TERM $$
+}
+
+# @cmd
+run() {
+    argc --argc-parallel "$0" term ::: ok
+}
+
+eval "$(argc --argc-eval "$0" "$@")"
+"#;
+    let tmpdir = tmpdir();
+    tmpdir.child("Argcfile.sh").write_str(source).unwrap();
+    let report_file = tmpdir.child("report.json");
+    let output = argc_command()
+        .current_dir(tmpdir.path())
+        .env("PATH", get_path_env_var())
+        .env(
+            "ARGC_PARALLEL_REPORT",
+            report_file.path().display().to_string(),
+        )
+        .arg("run")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(143));
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(report_file.path()).unwrap()).unwrap();
+    assert_eq!(report["exit"], 143);
+    assert_eq!(report["jobs"][0]["status"], "failed");
+    assert_eq!(report["jobs"][0]["exit"], 143);
+}
+
 #[test]
 fn script_path() {
     let tmpdir = tmpdir_argcfiles();
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .arg("--argc-script-path")
         .current_dir(tmpdir.child("dir1").path())
         .assert()
@@ -278,8 +1111,7 @@ fn script_path() {
 
 #[test]
 fn shell_path() {
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .arg("--argc-shell-path")
         .assert()
         .stdout(predicates::str::contains("bash"))
@@ -294,8 +1126,7 @@ fn run_argcfile() {
         if path.ends_with("EMPTY") {
             continue;
         }
-        Command::cargo_bin("argc")
-            .unwrap()
+        argc_command()
             .current_dir(tmpdir_path(&tmpdir, path).path().parent().unwrap())
             .env("PATH", path_env_var.clone())
             .assert()
@@ -303,8 +1134,7 @@ fn run_argcfile() {
             .success();
     }
 
-    Command::cargo_bin("argc")
-        .unwrap()
+    argc_command()
         .current_dir(tmpdir_path(&tmpdir, "dir1/subdir1/subdirdir1"))
         .env("PATH", path_env_var)
         .assert()
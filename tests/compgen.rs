@@ -1,5 +1,3 @@
-use crate::*;
-
 #[test]
 fn multiple() {
     let script = r###"
@@ -1162,3 +1160,25 @@ choice_val() {
         );
     }
 }
+
+#[test]
+fn hidden() {
+    let script = r###"
+# @flag --fa
+# @flag --debug
+# @hidden
+
+# @cmd
+cmda() { :; }
+
+# @cmd
+# @hidden
+internal() { :; }
+"###;
+
+    snapshot_compgen!(
+        script,
+        [vec!["prog", ""], vec!["prog", "--"]],
+        argc::Shell::Bash
+    );
+}
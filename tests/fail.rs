@@ -215,3 +215,143 @@ fn invalid_envs() {
         "@env(line 2) is invalid, can only be a single value"
     );
 }
+
+#[test]
+fn invalid_pattern() {
+    let script = r###"
+# @option --oa[/^(v\d+$/]
+    "###;
+    fail!(
+        script,
+        &["prog"],
+        "@option(line 2) is invalid, invalid pattern `^(v\\d+$`"
+    );
+}
+
+#[test]
+fn param_group_unknown_param() {
+    let script = r###"
+# @meta conflicts --json,--yaml
+# @flag --json
+    "###;
+    fail!(script, &["prog"], "@meta(line 2) unknown param `--yaml`");
+}
+
+#[test]
+fn deprecated_misplaced() {
+    let script = r###"
+# @arg foo
+# @deprecated
+    "###;
+    fail!(
+        script,
+        &["prog"],
+        "@deprecated(line 3) must follow @cmd, @alias, @flag or @option"
+    );
+}
+
+#[test]
+fn hidden_misplaced() {
+    let script = r###"
+# @describe A demo cli
+# @hidden
+    "###;
+    fail!(
+        script,
+        &["prog"],
+        "@hidden(line 3) must follow @cmd, @alias, @flag, @option, @arg or @env"
+    );
+}
+
+#[test]
+fn example_missing_command() {
+    let script = r###"
+# @example
+    "###;
+    fail!(script, &["prog"], "@example(line 2) missing command");
+}
+
+#[test]
+fn deps_cycle() {
+    let script = r###"
+# @cmd
+# @meta deps b
+a() { :; }
+
+# @cmd
+# @meta deps c
+b() { :; }
+
+# @cmd
+# @meta deps a
+c() { :; }
+    "###;
+    fail!(
+        script,
+        &["prog"],
+        "@meta(line 3) dependency cycle detected: a -> b -> c -> a"
+    );
+}
+
+#[test]
+fn deps_unknown() {
+    let script = r###"
+# @cmd
+# @meta deps missing
+a() { :; }
+    "###;
+    fail!(
+        script,
+        &["prog"],
+        "@meta(line 3) unknown dependency `missing`"
+    );
+}
+
+#[test]
+fn deps_misplaced() {
+    let script = r###"
+# @meta deps a
+# @cmd
+a() { :; }
+    "###;
+    fail!(script, &["prog"], "@meta(line 2) deps must follow @cmd");
+}
+
+#[test]
+fn targets_force_conflict() {
+    let script = r###"
+# @cmd
+# @meta targets out/app
+# @flag --force
+build() { :; }
+    "###;
+    fail!(
+        script,
+        &["prog"],
+        "@meta(line 3) targets conflicts with `--force`"
+    );
+}
+
+#[test]
+fn unsupported_locale() {
+    let script = r###"
+# @meta locale fr
+    "###;
+    fail!(
+        script,
+        &["prog"],
+        "@meta(line 2) unsupported locale `fr`, expected one of: en, zh"
+    );
+}
+
+#[test]
+fn unsupported_value_constraint() {
+    let script = r###"
+# @option --data {int,1..=x}
+    "###;
+    fail!(
+        script,
+        &["prog"],
+        "@option(line 2) is invalid, unsupported value constraint `{int,1..=x}`"
+    );
+}
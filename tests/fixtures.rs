@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
 
-use argc::Runtime;
-use assert_cmd::cargo::cargo_bin;
+use argc::{NativeRuntime, Runtime};
+use assert_cmd::cargo::{cargo_bin, CommandCargoExt};
 use assert_fs::fixture::{ChildPath, TempDir};
 use assert_fs::prelude::*;
 use rstest::fixture;
@@ -21,6 +23,110 @@ pub const SCRIPT_PATHS: [&str; 8] = [
     "dir6/ARGCFILE",
 ];
 
+/// Envs of every run, keep the output plain and in English whatever the terminal and locale of the tester
+pub const TEST_ENVS: [(&str, &str); 2] = [("NO_COLOR", "1"), ("LC_ALL", "C.UTF-8")];
+
+/// `NativeRuntime` that sees `TEST_ENVS`, for the in-process runs
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TestRuntime;
+
+impl Runtime for TestRuntime {
+    fn os(&self) -> String {
+        NativeRuntime.os()
+    }
+
+    fn shell_path(&self) -> anyhow::Result<String> {
+        NativeRuntime.shell_path()
+    }
+
+    fn bash_path(&self) -> Option<String> {
+        NativeRuntime.bash_path()
+    }
+
+    fn exec_bash_functions(
+        &self,
+        script_file: &str,
+        functions: &[&str],
+        args: &[String],
+        envs: HashMap<String, String>,
+    ) -> Option<Vec<String>> {
+        NativeRuntime.exec_bash_functions(script_file, functions, args, envs)
+    }
+
+    fn current_exe(&self) -> Option<String> {
+        NativeRuntime.current_exe()
+    }
+
+    fn current_dir(&self) -> Option<String> {
+        NativeRuntime.current_dir()
+    }
+
+    fn env_vars(&self) -> HashMap<String, String> {
+        let mut envs = NativeRuntime.env_vars();
+        envs.extend(TEST_ENVS.map(|(k, v)| (k.to_string(), v.to_string())));
+        envs
+    }
+
+    fn env_var(&self, name: &str) -> Option<String> {
+        match TEST_ENVS.iter().find(|(k, _)| *k == name) {
+            Some((_, v)) => Some(v.to_string()),
+            None => NativeRuntime.env_var(name),
+        }
+    }
+
+    fn which(&self, name: &str) -> Option<String> {
+        NativeRuntime.which(name)
+    }
+
+    fn exist_path(&self, path: &str) -> bool {
+        NativeRuntime.exist_path(path)
+    }
+
+    fn parent_path(&self, path: &str) -> Option<String> {
+        NativeRuntime.parent_path(path)
+    }
+
+    fn join_path(&self, path: &str, parts: &[&str]) -> String {
+        NativeRuntime.join_path(path, parts)
+    }
+
+    fn chdir(&self, cwd: &str, cd: &str) -> Option<String> {
+        NativeRuntime.chdir(cwd, cd)
+    }
+
+    fn metadata(&self, path: &str) -> Option<(bool, bool, bool)> {
+        NativeRuntime.metadata(path)
+    }
+
+    fn read_dir(&self, path: &str) -> Option<Vec<String>> {
+        NativeRuntime.read_dir(path)
+    }
+
+    fn read_to_string(&self, path: &str) -> Option<String> {
+        NativeRuntime.read_to_string(path)
+    }
+
+    fn modified(&self, path: &str) -> Option<SystemTime> {
+        NativeRuntime.modified(path)
+    }
+
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        NativeRuntime.read(path)
+    }
+
+    fn is_terminal(&self) -> bool {
+        NativeRuntime.is_terminal()
+    }
+}
+
+/// The argc binary with `TEST_ENVS`
+#[allow(dead_code)]
+pub fn argc_command() -> Command {
+    let mut command = Command::cargo_bin("argc").unwrap();
+    command.envs(TEST_ENVS);
+    command
+}
+
 pub fn locate_script(script_path: &str) -> String {
     let mut spec_path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     spec_path.push(script_path);
@@ -114,11 +220,12 @@ pub fn run_script<T: AsRef<Path>>(
 ) -> String {
     let path_env_var = get_path_env_var();
     let envs: HashMap<&str, &str> = envs.iter().cloned().collect();
-    let shell_path = argc::NativeRuntime.shell_path().unwrap();
-    let output = std::process::Command::new(shell_path)
+    let shell_path = TestRuntime.shell_path().unwrap();
+    let output = Command::new(shell_path)
         .arg(script_path.as_ref())
         .args(args)
         .env("PATH", path_env_var.clone())
+        .envs(TEST_ENVS)
         .envs(envs)
         .output()
         .unwrap();
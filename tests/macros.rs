@@ -20,7 +20,8 @@ macro_rules! fail {
         $err:expr
     ) => {
         let args: Vec<String> = $args.iter().map(|v| v.to_string()).collect();
-        let err = argc::eval(argc::NativeRuntime, $source, &args, None, None).unwrap_err();
+        let err =
+            argc::eval($crate::fixtures::TestRuntime, $source, &args, None, None).unwrap_err();
         assert_eq!(err.to_string().as_str(), $err);
     };
 }
@@ -40,7 +41,8 @@ macro_rules! snapshot {
 		$width:expr
     ) => {
         let args: Vec<String> = $args.iter().map(|v| v.to_string()).collect();
-        let values = argc::eval(argc::NativeRuntime, $source, &args, $path, $width).unwrap();
+        let values =
+            argc::eval($crate::fixtures::TestRuntime, $source, &args, $path, $width).unwrap();
         let shell_code = argc::ArgcValue::to_bash(&values);
         let build_script_dir = $crate::fixtures::tmpdir();
         let build_script_path = $crate::fixtures::build_script(&build_script_dir, $source);
@@ -78,7 +80,7 @@ macro_rules! snapshot_multi {
         for args in $matrix.iter() {
             let args: Vec<String> = args.iter().map(|v| v.to_string()).collect();
             let values = argc::eval(
-                argc::NativeRuntime,
+                $crate::fixtures::TestRuntime,
                 &script_content,
                 &args,
                 Some(script_path.as_str()),
@@ -120,7 +122,7 @@ macro_rules! snapshot_compgen {
         for args in $matrix.iter() {
             let args: Vec<String> = args.iter().map(|v| v.to_string()).collect();
             let words = match argc::compgen(
-                argc::NativeRuntime,
+                $crate::fixtures::TestRuntime,
                 $shell,
                 &script_path,
                 &script_content,
@@ -163,7 +165,7 @@ macro_rules! snapshot_compgen_shells {
         let args: Vec<String> = $args.iter().map(|v| v.to_string()).collect();
         for shell in argc::Shell::list() {
             let words = match argc::compgen(
-                argc::NativeRuntime,
+                $crate::fixtures::TestRuntime,
                 shell,
                 &script_path,
                 &script_content,
@@ -1,5 +1,3 @@
-use crate::*;
-
 #[test]
 fn dotenv() {
     let script = r###"
@@ -15,3 +13,128 @@ fn dotenv_custom_path() {
 "###;
     snapshot!(script, &["prog"]);
 }
+
+#[test]
+fn param_groups() {
+    let script = r###"
+# @meta conflicts --json,--yaml
+# @meta requires --key,--cert
+# @meta one-of-required --json,--yaml,--toml
+# @flag --json
+# @flag --yaml
+# @flag --toml
+# @option --key
+# @option --cert
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "--json"],
+            vec!["prog", "--toml", "--key", "k", "--cert", "c"],
+            vec!["prog", "--json", "--yaml"],
+            vec!["prog", "--json", "--key", "k"],
+            vec!["prog"],
+        ]
+    );
+}
+
+#[test]
+fn deps() {
+    let script = r###"
+# @cmd
+# @meta deps build,lint
+test() { echo test; }
+
+# @cmd
+# @meta deps compile
+build() { echo build; }
+
+# @cmd
+# @meta deps compile
+lint() { echo lint; }
+
+# @cmd
+compile() { echo compile; }
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "test"],
+            vec!["prog", "build"],
+            vec!["prog", "compile"]
+        ]
+    );
+}
+
+#[test]
+fn deps_parallel() {
+    let script = r###"
+# @cmd
+# @meta deps build,lint
+# @meta parallel-deps
+test() { echo test; }
+
+# @cmd
+build() { echo build; }
+
+# @cmd
+lint() { echo lint; }
+"###;
+    snapshot!(script, &["prog", "test"]);
+}
+
+#[test]
+fn sources_targets() {
+    let script = r###"
+# @cmd
+# @meta sources src/*.c
+# @meta targets out/app
+build() { :; }
+"###;
+    snapshot!(script, &["prog", "build", "-h"]);
+}
+
+#[test]
+fn locale() {
+    let script = r###"
+# @meta locale zh
+# @flag -v --verbose  Show more output
+# @option --mode[fast|slow]
+# @option --jobs {int,1..=8}
+# @flag --quiet
+# @deprecated
+# @arg target!
+# @env TOKEN!
+main() { :; }
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog"],
+            vec!["prog", "--mode", "fats", "foo"],
+            vec!["prog", "--verbos", "foo"],
+            vec!["prog", "--jobs", "9", "foo"],
+            vec!["prog", "-v", "-v", "foo"]
+        ]
+    );
+}
+
+#[test]
+fn locale_param_groups() {
+    let script = r###"
+# @meta locale zh
+# @cmd
+# @meta targets out/app
+# @meta conflicts --json,--yaml
+# @meta requires --key,--cert
+# @meta one-of-required --json,--yaml
+# @flag --json
+# @flag --yaml
+# @option --key
+# @option --cert
+build() { :; }
+"###;
+    snapshot_multi!(script, [vec!["prog", "build", "-h"]]);
+}
@@ -0,0 +1,96 @@
+use argc::{MatchError, ParseError};
+
+use crate::fixtures::TestRuntime;
+
+const SCRIPT: &str = r#"
+# @env MODE=dev
+# @flag -v --verbose*
+
+# @cmd
+# @option --jobs <NUM>
+# @option --tag*
+# @option -D-* <VALUE>
+# @arg files*
+build() { :; }
+
+# @cmd
+test() { :; }
+"#;
+
+fn parse(args: &[&str]) -> Result<argc::ParsedArgs, ParseError> {
+    let args: Vec<String> = args.iter().map(|v| v.to_string()).collect();
+    argc::parse(TestRuntime, SCRIPT, &args)
+}
+
+#[test]
+fn parse_values() {
+    let parsed = parse(&[
+        "prog", "-v", "-v", "build", "--jobs", "4", "--tag", "a", "--tag", "b", "-Dx=1", "f1", "f2",
+    ])
+    .unwrap();
+    assert_eq!(parsed.command_path(), ["build"]);
+    assert_eq!(parsed.command_fn(), Some("build"));
+    assert!(parsed.get_flag("verbose"));
+    assert_eq!(parsed.get_count("verbose"), 2);
+    assert_eq!(parsed.get("jobs"), Some("4"));
+    assert_eq!(parsed.get_parsed::<usize>("jobs").unwrap(), Some(4));
+    assert_eq!(parsed.get_many("tag").unwrap(), ["a", "b"]);
+    assert_eq!(parsed.get_map("D").unwrap()["x"], ["1"]);
+    assert_eq!(parsed.get_many("files").unwrap(), ["f1", "f2"]);
+    assert_eq!(parsed.positionals(), ["f1", "f2"]);
+    assert_eq!(parsed.get_env("MODE"), Some("dev"));
+    assert!(!parsed.contains("missing"));
+    assert_eq!(parsed.get_parsed::<usize>("missing").unwrap(), None);
+}
+
+#[test]
+fn parse_invalid_type() {
+    let parsed = parse(&["prog", "build", "--jobs", "many"]).unwrap();
+    let err = parsed.get_parsed::<usize>("jobs").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value `many` for `jobs`, invalid digit found in string"
+    );
+}
+
+#[test]
+fn parse_error() {
+    let err = parse(&["prog", "build", "--unknown"]).unwrap_err();
+    let ParseError::Match { kind, exit, .. } = err else {
+        panic!("unexpected error {err:?}");
+    };
+    assert!(matches!(
+        *kind,
+        MatchError::UnknownArgument { level: 1, arg, suggestion: None, .. } if arg == "--unknown"
+    ));
+    assert_eq!(exit, 1);
+
+    let err = parse(&["prog", "test", "--help"]).unwrap_err();
+    let ParseError::Match {
+        kind,
+        exit,
+        message,
+        ..
+    } = err
+    else {
+        panic!("unexpected error {err:?}");
+    };
+    assert_eq!(*kind, MatchError::DisplayHelp);
+    assert_eq!(exit, 0);
+    assert!(message.starts_with("USAGE: prog test"));
+}
+
+#[test]
+fn parse_invalid_input() {
+    assert_eq!(parse(&[]).unwrap_err(), ParseError::EmptyArgs);
+
+    let err = argc::parse(
+        TestRuntime,
+        "# @cmd
+# @cmd
+",
+        &["prog".to_string()],
+    )
+    .unwrap_err();
+    assert!(matches!(err, ParseError::InvalidScript(_)), "{err:?}");
+}
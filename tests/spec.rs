@@ -436,3 +436,176 @@ fn option_value_dash() {
         ]
     );
 }
+
+#[test]
+fn deprecated() {
+    let script = r###"
+# @flag --fa
+# @deprecated Use --fb instead
+# @flag --fb
+# @option --oa
+# @deprecated
+
+# @cmd
+# @alias a,old-a
+# @deprecated
+cmda() { :; }
+
+# @cmd
+# @deprecated Use cmda instead
+cmdb() { :; }
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "--fa", "--oa", "v", "cmda"],
+            vec!["prog", "old-a"],
+            vec!["prog", "a"],
+            vec!["prog", "cmdb"],
+            vec!["prog", "--fa", "cmdb", "-h"],
+        ]
+    );
+}
+
+#[test]
+fn deprecated_alias() {
+    let script = r###"
+# @cmd
+# @alias a
+# @alias old-a,oa
+# @deprecated Use a instead
+cmda() { :; }
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "a"],
+            vec!["prog", "old-a"],
+            vec!["prog", "oa"]
+        ]
+    );
+}
+
+#[test]
+fn hidden() {
+    let script = r###"
+# @flag --fa
+# @flag --debug
+# @hidden
+# @option --trace
+# @hidden
+# @env EA
+# @env DEBUG_LEVEL
+# @hidden
+
+# @cmd
+cmda() { :; }
+
+# @cmd
+# @hidden
+internal() { :; }
+
+# @cmd
+# @arg va
+# @arg vb
+# @hidden
+cmdb() { :; }
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "cmdb", "-h"],
+            vec!["prog", "--debug", "--trace", "x", "internal"],
+            vec!["prog", "cmdb", "a", "b"],
+        ]
+    );
+}
+
+#[test]
+fn hidden_with_alias() {
+    let script = r###"
+# @cmd
+build() { :; }
+
+# @cmd
+# @alias dbg
+# @hidden
+debug() { :; }
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "dbg"],
+            vec!["prog", "foo"],
+            vec!["prog", "help", "foo"]
+        ]
+    );
+}
+
+#[test]
+fn heading() {
+    let script = r###"
+# @flag --fa
+# @heading Advanced
+# @flag --fb
+# @option --oa
+# @heading
+# @flag --fc
+
+# @cmd
+cmda() { :; }
+
+# @heading Maintenance
+# @cmd
+# @option --oa
+# @heading Network
+# @option --ob
+clean() { :; }
+
+# @cmd
+# @alias up
+upgrade() { :; }
+"###;
+    snapshot_multi!(script, [vec!["prog", "-h"], vec!["prog", "clean", "-h"]]);
+}
+
+#[test]
+fn heading_options_and_subcommands() {
+    let script = r###"
+# @heading Output
+# @flag --json
+# @flag --yaml
+
+# @heading Setup
+# @cmd
+init() { :; }
+
+# @heading
+# @cmd
+build() { :; }
+
+# @cmd
+test() { :; }
+"###;
+    snapshot!(script, &["prog", "-h"]);
+}
+
+#[test]
+fn example() {
+    let script = r###"
+# @describe A demo cli
+# @example prog build --release
+# Build in release mode
+# @example prog test
+
+# @cmd
+# @flag --release
+# @example prog build --release
+build() { :; }
+"###;
+    snapshot_multi!(script, [vec!["prog", "-h"], vec!["prog", "build", "-h"]]);
+}
@@ -21,5 +21,6 @@ mod meta;
 mod misc;
 mod multiline;
 mod param_fn;
+mod parse;
 mod spec;
 mod validate;
@@ -360,3 +360,246 @@ main() { :; }
         [vec!["prog"], vec!["prog", "cmd1"], vec!["prog", "cmd2"],]
     );
 }
+
+#[test]
+fn value_type() {
+    let script = r###"
+# @option --oi {int}
+# @option --of {float}
+# @option --ob {bool}
+# @option --op {path}
+# @option --ofile {file}
+# @option --od {dir}
+# @option --ou {url}
+# @option --or {regex}
+# @option --oni*, {int}
+# @arg files* {file}
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec![
+                "prog",
+                "--oi=-12",
+                "--of",
+                "3.14",
+                "--ob",
+                "true",
+                "--op",
+                "src",
+                "--ofile",
+                "Cargo.toml",
+                "--od",
+                "src",
+                "--ou",
+                "https://example.com/a?b=c",
+                "--or",
+                "^v[0-9]+$",
+                "--oni",
+                "1,2",
+                "Cargo.toml",
+            ],
+            vec!["prog", "--oi", "1.0"],
+            vec!["prog", "--of", "1.2.3"],
+            vec!["prog", "--ob", "yes"],
+            vec!["prog", "--op", "not-exist"],
+            vec!["prog", "--ofile", "src"],
+            vec!["prog", "--od", "Cargo.toml"],
+            vec!["prog", "--ou", "example.com"],
+            vec!["prog", "--or", "(a"],
+            vec!["prog", "--oni", "1,x"],
+            vec!["prog", "Cargo.toml", "src"],
+        ]
+    );
+}
+
+#[test]
+fn value_type_after_notation() {
+    let script = r###"
+# @option --num <NUM> {int} A number
+# @option --fmt {json} output
+# @arg target <DIR> {dir}
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "--num", "x"],
+            vec!["prog", "Cargo.toml"]
+        ]
+    );
+}
+
+#[test]
+fn value_range() {
+    let script = r###"
+# @option --port {int,1..=65535}
+# @option --ratio {0..1}
+# @option --name {len=..=8}
+# @option --tags*, {len=2..}
+# @arg level {-3..=3}
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec![
+                "prog", "--port", "8080", "--ratio", "0.5", "--name", "abcdefgh", "--tags",
+                "ab,cd", "--", "-3"
+            ],
+            vec!["prog", "--port", "0"],
+            vec!["prog", "--port", "65536"],
+            vec!["prog", "--ratio", "1"],
+            vec!["prog", "--ratio", "x"],
+            vec!["prog", "--name", "abcdefghi"],
+            vec!["prog", "--tags", "ab,c"],
+            vec!["prog", "4"],
+        ]
+    );
+}
+
+#[test]
+fn value_pattern() {
+    let script = r###"
+# @option --tag[/^v\d+\.\d+$/]
+# @option --ids*,[/^[\da-f]+$/]
+# @option --name[/^\w+$/] {len=..=8}
+# @arg words*[/^[^\s]+$/]
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "-h"],
+            vec!["prog", "--tag", "v1.2", "--ids", "ab12,ff", "--name", "foo_bar", "a", "b"],
+            vec!["prog", "--tag", "1.2"],
+            vec!["prog", "--tag", "v1.2.3"],
+            vec!["prog", "--ids", "ab,xy"],
+            vec!["prog", "--name", "foo-bar"],
+            vec!["prog", "a b"],
+        ]
+    );
+}
+
+#[test]
+fn match_error() {
+    let script = r###"
+# @cmd
+# @option --mode[debug|release]
+build() { :; }
+
+# @cmd
+test() { :; }
+"###;
+    let eval = |args: &[&str]| {
+        let args: Vec<String> = args.iter().map(|v| v.to_string()).collect();
+        argc::eval(crate::fixtures::TestRuntime, script, &args, None, None).unwrap()
+    };
+
+    let mut values = eval(&["prog", "build", "--mode", "dev"]);
+    let argc::ArgcValue::Error((message, exit, err)) = &mut values[0] else {
+        panic!("expect error");
+    };
+    let argc::MatchError::InvalidValue {
+        level,
+        value,
+        param,
+        choices,
+        ..
+    } = &*err
+    else {
+        panic!("expect invalid value");
+    };
+    assert_eq!(
+        (*level, value.as_str(), param.as_str(), choices.as_slice()),
+        (
+            1,
+            "dev",
+            "<MODE>",
+            ["debug".to_string(), "release".to_string()].as_slice()
+        )
+    );
+    assert_eq!(*exit, 1);
+    assert_eq!(message, &err.to_string());
+    *message = format!("'{value}' ist ungültig, erlaubt: {}", choices.join("|"));
+    assert_eq!(
+        argc::ArgcValue::to_bash(&values),
+        "command cat >&2 <<-'EOF' \n'dev' ist ungültig, erlaubt: debug|release\nEOF\nexit 1"
+    );
+
+    let values = eval(&["prog", "deploy"]);
+    let argc::ArgcValue::Error((_, _, err)) = &values[0] else {
+        panic!("expect error");
+    };
+    let argc::MatchError::InvalidSubcommand {
+        arg,
+        cmd,
+        subcommands,
+        ..
+    } = err
+    else {
+        panic!("expect invalid subcommand");
+    };
+    assert_eq!(arg.as_deref(), Some("deploy"));
+    assert_eq!(cmd, "prog");
+    assert_eq!(subcommands, &["build", "test"]);
+    assert_eq!(err.exit_code(), 1);
+}
+
+#[test]
+fn did_you_mean() {
+    let script = r###"
+# @cmd
+# @flag --verbose
+# @option --mode[debug|release]
+build() { :; }
+
+# @cmd
+test() { :; }
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "biuld"],
+            vec!["prog", "help", "tets"],
+            vec!["prog", "build", "--verbsoe"],
+            vec!["prog", "build", "--mode", "relaese"],
+            vec!["prog", "deploy"],
+        ]
+    );
+}
+
+#[test]
+fn did_you_mean_hidden_deprecated() {
+    let script = r###"
+# @cmd
+# @flag --verbose
+# @hidden
+# @flag --verbosity
+# @deprecated
+build() { :; }
+
+# @cmd
+# @alias tests
+# @deprecated Use test instead
+test() { :; }
+
+# @cmd
+# @hidden
+deploy() { :; }
+
+# @cmd
+# @deprecated Use deploy instead
+upload() { :; }
+"###;
+    snapshot_multi!(
+        script,
+        [
+            vec!["prog", "deplo"],
+            vec!["prog", "uplod"],
+            vec!["prog", "help", "tests2"],
+            vec!["prog", "build", "--verbos"],
+            vec!["prog", "build", "--verbosty"],
+        ]
+    );
+}